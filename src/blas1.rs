@@ -0,0 +1,338 @@
+//! BLAS level 1, shaped like the reference Fortran so ports map one to
+//! one.
+//!
+//! Every routine takes the element count `n` and, for each vector, an
+//! increment (`incx`, `incy`) saying how far apart its elements sit.
+//! Pass `1` for a plain slice. A negative increment walks the vector
+//! backwards from element `(n - 1) * |inc|`, the same as the reference
+//! implementation; `nrm2`, `asum`, `iamax` and `scal` follow BLAS in
+//! treating a non-positive `incx` as an empty vector.
+//!
+//! Indices are 0-based, `iamax` included, and a vector that's too short
+//! for `n` and its increment panics like any out-of-range index would.
+
+use crate::dot_slice;
+use crate::norm::scaled_sum_sq;
+use crate::num::{Float, Num};
+
+/// Where the `i`th of `n` elements sits for increment `inc`.
+fn at(i: usize, n: usize, inc: isize) -> usize {
+    if inc >= 0 {
+        i * inc as usize
+    } else {
+        (n - 1 - i) * inc.unsigned_abs()
+    }
+}
+
+/// The `n` positions a non-negative-only routine visits, or none.
+fn positive(n: usize, inc: isize) -> impl Iterator<Item = usize> {
+    let n = if inc > 0 { n } else { 0 };
+    (0..n).map(move |i| i * inc as usize)
+}
+
+/// # dot()
+/// Takes `n`, `x` with `incx` and `y` with `incy`.
+///
+/// Returns `Σ x[i]·y[i]`. With both increments
+/// 1 it's `dot_slice()` (SIMD and all) on the
+/// first `n` elements.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::dot;
+///
+/// let x = [1, 2, 3, 4];
+/// // x[0]·x[2] + x[2]·x[0]
+/// assert_eq!(6, dot(2, &x, 2, &x, -2));
+/// ```
+pub fn dot<T: Num>(n: usize, x: &[T], incx: isize, y: &[T], incy: isize) -> T {
+    if n == 0 {
+        return T::ZERO;
+    }
+    if incx == 1 && incy == 1 {
+        return dot_slice(&x[..n], &y[..n]);
+    }
+    (0..n).fold(T::ZERO, |acc, i| {
+        acc + x[at(i, n, incx)] * y[at(i, n, incy)]
+    })
+}
+
+/// # axpy()
+/// Takes `n`, a scalar `alpha`, `x` with `incx`
+/// and `y` with `incy`.
+///
+/// Overwrites `y` with `alpha·x + y`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::axpy;
+///
+/// let mut y = [1, 1, 1];
+/// axpy(3, 2, &[1, 2, 3], 1, &mut y, 1);
+/// assert_eq!([3, 5, 7], y);
+/// ```
+pub fn axpy<T: Num>(n: usize, alpha: T, x: &[T], incx: isize, y: &mut [T], incy: isize) {
+    if n == 0 || alpha == T::ZERO {
+        return;
+    }
+    for i in 0..n {
+        let iy = at(i, n, incy);
+        y[iy] = alpha * x[at(i, n, incx)] + y[iy];
+    }
+}
+
+/// # scal()
+/// Takes `n`, a scalar `alpha` and `x` with `incx`.
+///
+/// Overwrites `x` with `alpha·x`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::scal;
+///
+/// let mut x = [1, 2, 3, 4];
+/// scal(2, 10, &mut x, 2);
+/// assert_eq!([10, 2, 30, 4], x);
+/// ```
+pub fn scal<T: Num>(n: usize, alpha: T, x: &mut [T], incx: isize) {
+    for i in positive(n, incx) {
+        x[i] = alpha * x[i];
+    }
+}
+
+/// # swap()
+/// Takes `n`, `x` with `incx` and `y` with `incy`.
+///
+/// Swaps the elements of `x` and `y`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::swap;
+///
+/// let (mut x, mut y) = ([1, 2, 3], [4, 5, 6]);
+/// swap(3, &mut x, 1, &mut y, -1);
+/// assert_eq!(([6, 5, 4], [3, 2, 1]), (x, y));
+/// ```
+pub fn swap<T: Copy>(n: usize, x: &mut [T], incx: isize, y: &mut [T], incy: isize) {
+    for i in 0..n {
+        let (ix, iy) = (at(i, n, incx), at(i, n, incy));
+        std::mem::swap(&mut x[ix], &mut y[iy]);
+    }
+}
+
+/// # copy()
+/// Takes `n`, `x` with `incx` and `y` with `incy`.
+///
+/// Copies `x` into `y`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::copy;
+///
+/// let mut y = [0; 3];
+/// copy(3, &[1, 2, 3, 4, 5, 6], 2, &mut y, 1);
+/// assert_eq!([1, 3, 5], y);
+/// ```
+pub fn copy<T: Copy>(n: usize, x: &[T], incx: isize, y: &mut [T], incy: isize) {
+    if incx == 1 && incy == 1 {
+        y[..n].copy_from_slice(&x[..n]);
+        return;
+    }
+    for i in 0..n {
+        y[at(i, n, incy)] = x[at(i, n, incx)];
+    }
+}
+
+/// # nrm2()
+/// Takes `n` and `x` with `incx`.
+///
+/// Returns the Euclidean norm of `x`, scaled
+/// as it goes like `norm::l2_norm()`, so it
+/// doesn't overflow.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::nrm2;
+///
+/// assert_eq!(5.0, nrm2(2, &[3.0, 9.0, 4.0], 2));
+/// let big: f64 = nrm2(2, &[3e300, 4e300], 1);
+/// assert!((big / 5e300 - 1.0).abs() < 1e-15);
+/// ```
+pub fn nrm2<T: Float>(n: usize, x: &[T], incx: isize) -> T {
+    let (scale, ssq) = scaled_sum_sq(positive(n, incx).map(|i| x[i]));
+    scale * ssq.sqrt()
+}
+
+/// # asum()
+/// Takes `n` and `x` with `incx`.
+///
+/// Returns the sum of the absolute values.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::asum;
+///
+/// assert_eq!(6.0, asum(3, &[1.0, -2.0, 3.0], 1));
+/// ```
+pub fn asum<T: Float>(n: usize, x: &[T], incx: isize) -> T {
+    positive(n, incx).fold(T::ZERO, |acc, i| acc + x[i].abs())
+}
+
+/// # iamax()
+/// Takes `n` and `x` with `incx`.
+///
+/// Returns the (0-based, in steps of `incx`)
+/// index of the first element with the largest
+/// absolute value, or None if there aren't any.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::iamax;
+///
+/// assert_eq!(Some(1), iamax(3, &[1.0, -7.0, 7.0], 1));
+/// assert_eq!(Some(2), iamax(3, &[1.0, -7.0, 2.0, 0.0, 3.0], 2));
+/// assert_eq!(None, iamax::<f64>(0, &[], 1));
+/// ```
+pub fn iamax<T: Float>(n: usize, x: &[T], incx: isize) -> Option<usize> {
+    let mut best: Option<(usize, T)> = None;
+    for (k, i) in positive(n, incx).enumerate() {
+        let v = x[i].abs();
+        let better = match best {
+            Some((_, b)) => v > b,
+            None => true,
+        };
+        if better {
+            best = Some((k, v));
+        }
+    }
+    best.map(|(k, _)| k)
+}
+
+/// # rot()
+/// Takes `n`, `x` with `incx`, `y` with `incy`
+/// and a rotation `c`, `s` (say from `rotg()`).
+///
+/// Applies the plane rotation to every pair:
+/// `x ← c·x + s·y`, `y ← c·y - s·x`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::rot;
+///
+/// let (mut x, mut y) = ([1.0, 2.0], [3.0, 4.0]);
+/// rot(2, &mut x, 1, &mut y, 1, 0.0, 1.0);
+/// assert_eq!(([3.0, 4.0], [-1.0, -2.0]), (x, y));
+/// ```
+pub fn rot<T: Float>(n: usize, x: &mut [T], incx: isize, y: &mut [T], incy: isize, c: T, s: T) {
+    for i in 0..n {
+        let (ix, iy) = (at(i, n, incx), at(i, n, incy));
+        let (a, b) = (x[ix], y[iy]);
+        x[ix] = c * a + s * b;
+        y[iy] = c * b - s * a;
+    }
+}
+
+/// # rotg()
+/// Takes mutable references to `a` and `b`.
+///
+/// Builds the Givens rotation that zeroes `b`:
+/// returns `(c, s)` with `c·a + s·b = r` and
+/// `c·b - s·a = 0`, and overwrites `a` with `r`
+/// and `b` with the BLAS reconstruction value `z`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::blas1::rotg;
+///
+/// let (mut a, mut b) = (3.0, 4.0);
+/// let (c, s) = rotg(&mut a, &mut b);
+/// assert_eq!((5.0, 0.6, 0.8), (a, c, s));
+/// ```
+pub fn rotg<T: Float>(a: &mut T, b: &mut T) -> (T, T) {
+    let (anorm, bnorm) = (a.abs(), b.abs());
+    if bnorm == T::ZERO {
+        *b = T::ZERO;
+        return (T::ONE, T::ZERO);
+    }
+    if anorm == T::ZERO {
+        *a = *b;
+        *b = T::ONE;
+        return (T::ZERO, T::ONE);
+    }
+    let scale = if anorm > bnorm { anorm } else { bnorm };
+    let roe = if anorm > bnorm { *a } else { *b };
+    let (sa, sb) = (*a / scale, *b / scale);
+    let r = scale * (sa * sa + sb * sb).sqrt();
+    let r = if roe < T::ZERO { -r } else { r };
+    let (c, s) = (*a / r, *b / r);
+    *b = if anorm > bnorm {
+        s
+    } else if c != T::ZERO {
+        T::ONE / c
+    } else {
+        T::ONE
+    };
+    *a = r;
+    (c, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment_check() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        assert_eq!(1.0 + 3.0 + 5.0, asum(3, &x, 2));
+        assert_eq!(0.0, asum(3, &x, 0));
+        assert_eq!(0.0, nrm2(3, &x, -1));
+        assert_eq!(None, iamax(3, &x, -1));
+        let mut y = [0.0; 3];
+        copy(3, &x, -2, &mut y, 1);
+        assert_eq!([5.0, 3.0, 1.0], y);
+        axpy(2, 1.0, &x, 1, &mut y, -2);
+        assert_eq!([7.0, 3.0, 2.0], y);
+        assert_eq!(7.0 + 3.0 * 2.0 + 2.0 * 3.0, dot(3, &y, 1, &x, 1));
+    }
+
+    #[test]
+    fn dot_check() {
+        let x: Vec<f64> = (0..100).map(|k| k as f64).collect();
+        let y: Vec<f64> = (0..100).map(|k| (k % 7) as f64).collect();
+        assert_eq!(crate::dot_slice(&x[..50], &y[..50]), dot(50, &x, 1, &y, 1));
+        let rev: f64 = (0..10).map(|i| x[3 * i] * y[9 - i]).sum();
+        assert_eq!(rev, dot(10, &x, 3, &y, -1));
+        assert_eq!(0, dot::<i32>(0, &[], 1, &[], 1));
+    }
+
+    #[test]
+    fn rot_check() {
+        for &(a0, b0) in &[
+            (3.0, 4.0),
+            (-4.0, 3.0),
+            (1.0, -1e-3),
+            (0.0, 2.0),
+            (2.0, 0.0),
+        ] {
+            let (mut a, mut b) = (a0, b0);
+            let (c, s) = rotg(&mut a, &mut b);
+            assert!((c * c + s * s - 1.0f64).abs() < 1e-15);
+            let (mut x, mut y) = ([a0], [b0]);
+            rot(1, &mut x, 1, &mut y, 1, c, s);
+            assert!((x[0] - a).abs() < 1e-15 && y[0].abs() < 1e-15);
+        }
+        let (mut x, mut y) = ([1.0, 0.0, 2.0], [5.0, 6.0]);
+        rot(2, &mut x, 2, &mut y, -1, 0.0, 1.0);
+        assert_eq!(([6.0, 0.0, 5.0], [-2.0, -1.0]), (x, y));
+    }
+
+    #[test]
+    fn integer_check() {
+        let mut x = [1, 2, 3];
+        scal(3, -1, &mut x, 1);
+        assert_eq!([-1, -2, -3], x);
+        let mut y = [10, 20, 30];
+        swap(3, &mut x, 1, &mut y, 1);
+        assert_eq!(([10, 20, 30], [-1, -2, -3]), (x, y));
+    }
+}
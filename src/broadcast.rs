@@ -0,0 +1,132 @@
+//! Scalars standing in for slices.
+//!
+//! The element-wise ops (`mul_slice()`, `add_slice_into()`,
+//! `sub_assign_slice()` and the rest) take `Broadcast` input, which is
+//! anything `SliceInput` plus plain scalars. A scalar stretches to the
+//! length of whatever's on the other side, so `mul_slice(&v, &2.0)`
+//! scales `v` without building `vec![2.0; n]` first.
+//!
+//! The primitive numbers and `bool` are `Broadcast` as they are; wrap
+//! anything else (a `Wrapping<u8>`, your own `Num` type) in `Scalar`.
+//! Literals sometimes need a suffix (`&2u8`) so the compiler knows
+//! which scalar you mean; `mul_scalar()` and friends take the scalar
+//! by value and don't have that problem.
+
+use crate::strided::SliceInput;
+
+/// # Broadcast
+/// Input to an element-wise op: either a run
+/// of elements or a single value that repeats
+/// as far as needed.
+pub trait Broadcast {
+    type Item: Copy;
+
+    /// How many elements there are, or None for a
+    /// scalar, which fits any length.
+    fn extent(&self) -> Option<usize>;
+
+    /// Element `i`; a scalar ignores `i`.
+    fn item(&self, i: usize) -> Self::Item;
+
+    /// The elements as one plain slice, if they're
+    /// laid out like that. Turns on the fast paths.
+    fn contiguous(&self) -> Option<&[Self::Item]> {
+        None
+    }
+}
+
+impl<S: SliceInput + ?Sized> Broadcast for S {
+    type Item = S::Item;
+
+    fn extent(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn item(&self, i: usize) -> S::Item {
+        self.at(i)
+    }
+
+    fn contiguous(&self) -> Option<&[S::Item]> {
+        self.as_contiguous()
+    }
+}
+
+/// # Scalar
+/// Wraps any value so it broadcasts like the
+/// primitive numbers do.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::{add_slice, Scalar};
+/// use std::num::Wrapping;
+///
+/// let t = [Wrapping(250u8), Wrapping(1)];
+/// let q = add_slice(&t, &Scalar(Wrapping(10)));
+/// assert_eq!(vec![Wrapping(4), Wrapping(11)], q);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Scalar<T>(pub T);
+
+impl<T: Copy> Broadcast for Scalar<T> {
+    type Item = T;
+
+    fn extent(&self) -> Option<usize> {
+        None
+    }
+
+    fn item(&self, _: usize) -> T {
+        self.0
+    }
+}
+
+macro_rules! broadcast_scalar {
+    ($($t:ty),*) => {$(
+        impl Broadcast for $t {
+            type Item = $t;
+
+            fn extent(&self) -> Option<usize> {
+                None
+            }
+
+            fn item(&self, _: usize) -> $t {
+                *self
+            }
+        }
+    )*};
+}
+
+broadcast_scalar!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool);
+
+/// How many elements an op over `a` and `b` produces: the shorter
+/// of the two, a scalar fitting the other side, and a single element
+/// if both are scalars.
+pub(crate) fn extent<A, B>(a: &A, b: &B) -> usize
+where
+    A: Broadcast + ?Sized,
+    B: Broadcast + ?Sized,
+{
+    match (a.extent(), b.extent()) {
+        (Some(x), Some(y)) => x.min(y),
+        (Some(x), None) | (None, Some(x)) => x,
+        (None, None) => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StridedSlice;
+
+    #[test]
+    fn extent_check() {
+        let v = vec![1, 2, 3];
+        assert_eq!(3, extent(&v, &5));
+        assert_eq!(2, extent(&Scalar(1), &[1, 2][..]));
+        assert_eq!(1, extent(&1.0, &2.0));
+        let s = StridedSlice::new(&v, 2, 2).unwrap();
+        assert_eq!(2, extent(&s, &v));
+        assert_eq!(Some(&v[..]), v.contiguous());
+        assert_eq!(None, 7u8.contiguous());
+        assert!(true.item(99));
+    }
+}
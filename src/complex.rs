@@ -0,0 +1,362 @@
+//! Complex numbers, for IQ samples and spectra.
+//!
+//! `Complex<T>` is a `Num` whenever `T` is, so `dot_slice()`,
+//! `mul_slice()`, the reductions and the rest work on complex slices
+//! as they are; `Complex<i16>` is fine for raw IQ and `Complex<f32>`
+//! for everything after. This module adds the operations that only
+//! make sense for complex input: the conjugated dot product, the
+//! conjugate, magnitude and phase.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::broadcast::Broadcast;
+use crate::num::{Field, Float, Num, One, Zero};
+use crate::strided::SliceInput;
+
+/// # Complex
+/// `re + im·i`, laid out as two `T`s side by
+/// side (`#[repr(C)]`), the same as C's and
+/// Fortran's complex types.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::Complex;
+/// use slicenator::dot_slice;
+///
+/// let i = Complex::new(0, 1);
+/// assert_eq!(Complex::new(-1, 0), i * i);
+/// let a = [Complex::new(1, 2), Complex::new(3, -1)];
+/// assert_eq!(Complex::new(5, -2), dot_slice(&a, &a));
+/// assert_eq!("3-1i", a[1].to_string());
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complex<T> {
+    pub const fn new(re: T, im: T) -> Self {
+        Complex { re, im }
+    }
+}
+
+impl<T: Num> Complex<T> {
+    /// `re² + im²`, the magnitude squared. No square
+    /// root, so it works for integers too.
+    pub fn norm_sqr(self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl<T: Num + Neg<Output = T>> Complex<T> {
+    /// `re - im·i`.
+    pub fn conj(self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+}
+
+impl<T: Float> Complex<T> {
+    /// `r·(cos θ + i·sin θ)`.
+    pub fn from_polar(r: T, theta: T) -> Self {
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// `e^(iθ)`, a point on the unit circle.
+    pub fn cis(theta: T) -> Self {
+        Complex::new(theta.cos(), theta.sin())
+    }
+
+    /// The magnitude `|z|`, without overflowing on the way.
+    pub fn abs(self) -> T {
+        self.re.hypot(self.im)
+    }
+
+    /// The phase angle, in `(-π, π]`.
+    pub fn arg(self) -> T {
+        self.im.atan2(self.re)
+    }
+}
+
+impl<T: Zero> Zero for Complex<T> {
+    const ZERO: Self = Complex::new(T::ZERO, T::ZERO);
+}
+
+impl<T: Zero + One> One for Complex<T> {
+    const ONE: Self = Complex::new(T::ONE, T::ZERO);
+}
+
+impl<T: Num> Num for Complex<T> {}
+
+impl<T: Field> Field for Complex<T> {}
+
+impl<T: Num> From<T> for Complex<T> {
+    fn from(re: T) -> Self {
+        Complex::new(re, T::ZERO)
+    }
+}
+
+impl<T: Num> Add for Complex<T> {
+    type Output = Self;
+
+    fn add(self, o: Self) -> Self {
+        Complex::new(self.re + o.re, self.im + o.im)
+    }
+}
+
+impl<T: Num> Sub for Complex<T> {
+    type Output = Self;
+
+    fn sub(self, o: Self) -> Self {
+        Complex::new(self.re - o.re, self.im - o.im)
+    }
+}
+
+impl<T: Num> Mul for Complex<T> {
+    type Output = Self;
+
+    fn mul(self, o: Self) -> Self {
+        Complex::new(
+            self.re * o.re - self.im * o.im,
+            self.re * o.im + self.im * o.re,
+        )
+    }
+}
+
+/// The textbook formula, `(a·conj(b)) / |b|²`. Works for
+/// any `Num` (integers round towards zero per part), but
+/// `|b|²` can overflow where the quotient wouldn't.
+impl<T: Num> Div for Complex<T> {
+    type Output = Self;
+
+    fn div(self, o: Self) -> Self {
+        let d = o.norm_sqr();
+        Complex::new(
+            (self.re * o.re + self.im * o.im) / d,
+            (self.im * o.re - self.re * o.im) / d,
+        )
+    }
+}
+
+impl<T: Num> Mul<T> for Complex<T> {
+    type Output = Self;
+
+    fn mul(self, k: T) -> Self {
+        Complex::new(self.re * k, self.im * k)
+    }
+}
+
+impl<T: Num> Div<T> for Complex<T> {
+    type Output = Self;
+
+    fn div(self, k: T) -> Self {
+        Complex::new(self.re / k, self.im / k)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Complex<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl<T: Num> Sum for Complex<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, z| acc + z)
+    }
+}
+
+impl<'a, T: Num> Sum<&'a Complex<T>> for Complex<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, &z| acc + z)
+    }
+}
+
+/// A lone complex number broadcasts like the primitive scalars.
+impl<T: Copy> Broadcast for Complex<T> {
+    type Item = Complex<T>;
+
+    fn extent(&self) -> Option<usize> {
+        None
+    }
+
+    fn item(&self, _: usize) -> Complex<T> {
+        *self
+    }
+}
+
+/// `1+2i`, `3-1i`; a precision applies to both parts.
+impl<T: fmt::Display> fmt::Display for Complex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "{:.*}{:+.*}i", p, self.re, p, self.im),
+            None => write!(f, "{}{:+}i", self.re, self.im),
+        }
+    }
+}
+
+/// # dotc_slice()
+/// Takes a reference to a couple of complex
+/// slices (or anything `SliceInput`).
+///
+/// Returns the conjugated dot product
+/// `Σ conj(a[i])·b[i]`, BLAS `dotc`. Only goes
+/// up to the shortest slice.
+///
+/// `dotc_slice(&a, &a)` is `‖a‖²` (with a zero
+/// imaginary part), which plain `dot_slice()`
+/// isn't.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::{dotc_slice, Complex};
+///
+/// let a = [Complex::new(1, 2), Complex::new(3, -1)];
+/// assert_eq!(Complex::new(15, 0), dotc_slice(&a, &a));
+/// let b = [Complex::new(0, 1), Complex::new(1, 0)];
+/// assert_eq!(Complex::new(5, 2), dotc_slice(&a, &b));
+/// ```
+pub fn dotc_slice<T: Num>(
+    a: &(impl SliceInput<Item = Complex<T>> + ?Sized),
+    b: &(impl SliceInput<Item = Complex<T>> + ?Sized),
+) -> Complex<T> {
+    (0..a.len().min(b.len())).fold(Complex::ZERO, |acc, i| {
+        let (x, y) = (a.at(i), b.at(i));
+        Complex::new(
+            acc.re + x.re * y.re + x.im * y.im,
+            acc.im + x.re * y.im - x.im * y.re,
+        )
+    })
+}
+
+/// # conj_slice()
+/// Takes a reference to a complex slice.
+///
+/// Returns a new shiny Vec of the complex
+/// conjugates.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::{conj_slice, Complex};
+///
+/// let q = conj_slice(&[Complex::new(1.0, 2.0), Complex::new(3.0, -1.0)]);
+/// assert_eq!(vec![Complex::new(1.0, -2.0), Complex::new(3.0, 1.0)], q);
+/// ```
+pub fn conj_slice<T: Num + Neg<Output = T>>(
+    a: &(impl SliceInput<Item = Complex<T>> + ?Sized),
+) -> Vec<Complex<T>> {
+    (0..a.len()).map(|i| a.at(i).conj()).collect()
+}
+
+/// # conj_assign_slice()
+/// Same as `conj_slice()` but conjugates `a`
+/// in place.
+pub fn conj_assign_slice<T: Num + Neg<Output = T>>(a: &mut [Complex<T>]) {
+    for z in a {
+        z.im = -z.im;
+    }
+}
+
+/// # magnitude_slice()
+/// Takes a reference to a complex slice.
+///
+/// Returns a new shiny Vec of the magnitudes
+/// `|z|`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::{magnitude_slice, Complex};
+///
+/// let q = magnitude_slice(&[Complex::new(3.0, 4.0), Complex::new(0.0, -2.0)]);
+/// assert_eq!(vec![5.0, 2.0], q);
+/// ```
+pub fn magnitude_slice<T: Float>(a: &(impl SliceInput<Item = Complex<T>> + ?Sized)) -> Vec<T> {
+    (0..a.len()).map(|i| a.at(i).abs()).collect()
+}
+
+/// # phase_slice()
+/// Takes a reference to a complex slice.
+///
+/// Returns a new shiny Vec of the phase
+/// angles, in radians in `(-π, π]`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::{phase_slice, Complex};
+///
+/// let q = phase_slice(&[Complex::new(1.0, 0.0), Complex::new(0.0, 1.0)]);
+/// assert_eq!(vec![0.0, std::f64::consts::FRAC_PI_2], q);
+/// ```
+pub fn phase_slice<T: Float>(a: &(impl SliceInput<Item = Complex<T>> + ?Sized)) -> Vec<T> {
+    (0..a.len()).map(|i| a.at(i).arg()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mul_slice, StridedSlice};
+
+    fn c(re: f64, im: f64) -> Complex<f64> {
+        Complex::new(re, im)
+    }
+
+    #[test]
+    fn arithmetic_check() {
+        let (a, b) = (c(1.0, 2.0), c(3.0, -4.0));
+        assert_eq!(c(4.0, -2.0), a + b);
+        assert_eq!(c(-2.0, 6.0), a - b);
+        assert_eq!(c(11.0, 2.0), a * b);
+        assert_eq!(a, (a * b) / b);
+        assert_eq!(c(2.0, 4.0), a * 2.0);
+        assert_eq!(c(-1.0, -2.0), -a);
+        assert_eq!(5.0, b.abs());
+        assert_eq!(25.0, b.norm_sqr());
+        assert_eq!(Complex::from(7.0), c(7.0, 0.0));
+        assert_eq!(c(4.0, 0.0), [a, a.conj(), c(2.0, 0.0)].iter().sum());
+        let z = Complex::from_polar(2.0, std::f64::consts::FRAC_PI_4);
+        assert!((z.arg() - std::f64::consts::FRAC_PI_4).abs() < 1e-15);
+        assert!((z.abs() - 2.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn slice_ops_check() {
+        let a = vec![c(1.0, 1.0), c(0.0, -2.0), c(-3.0, 0.5)];
+        let i = c(0.0, 1.0);
+        assert_eq!(
+            vec![c(-1.0, 1.0), c(2.0, 0.0), c(-0.5, -3.0)],
+            mul_slice(&a, &i)
+        );
+        let n = dotc_slice::<f64>(&a, &a);
+        assert_eq!(c(2.0 + 4.0 + 9.25, 0.0), n);
+        let cj = conj_slice(&a);
+        assert_eq!(n, crate::dot_slice(&cj, &a));
+        let mut m = a.clone();
+        conj_assign_slice(&mut m);
+        assert_eq!(cj, m);
+
+        let back = StridedSlice::new(&a, 3, -1).unwrap();
+        let m = magnitude_slice(&back);
+        let e = [9.25f64.sqrt(), 2.0, 2.0f64.sqrt()];
+        assert!(m.iter().zip(e).all(|(m, e)| (m - e).abs() < 1e-15));
+        let p = phase_slice(&a);
+        assert!((p[1] + std::f64::consts::FRAC_PI_2).abs() < 1e-15);
+    }
+
+    #[test]
+    fn integer_check() {
+        let iq = [Complex::new(100i16, -20), Complex::new(-5, 7)];
+        assert_eq!(Complex::new(10000 + 400 + 25 + 49, 0), dotc_slice(&iq, &iq));
+        assert_eq!(
+            Complex::new(3, 2),
+            Complex::new(6i32, 4) / Complex::new(2, 0)
+        );
+        assert_eq!(Complex::new(3, 0), Complex::new(7i32, 1) / 2);
+        assert_eq!("1.50+0.25i", format!("{:.2}", c(1.5, 0.25)));
+        assert_eq!("-1-2i", Complex::new(-1, -2).to_string());
+    }
+}
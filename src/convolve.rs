@@ -0,0 +1,414 @@
+//! Convolution and cross-correlation, of slices and of matrices.
+//!
+//! Each output element of a convolution is a `dot_slice()` of the
+//! signal against the kernel reversed, slid one step further along;
+//! `convolve_direct()` does exactly that, for any `Num`. For long
+//! inputs it's cheaper to multiply spectra instead, which is
+//! `convolve_fft()`. `convolve()` and `correlate()` pick whichever
+//! should be faster for the sizes you hand them.
+//!
+//! `ConvolveMode` says how much of the result you want, and works
+//! like SciPy's `mode`: all of it, the part lined up with the first
+//! argument, or only where the two overlap completely.
+//!
+//! `convolve2d()` and `correlate2d()` do the same over matrix views,
+//! for blurring or edge-detecting an image with a small kernel.
+
+use crate::complex::Complex;
+use crate::dot_slice;
+use crate::fft::RealFftPlan;
+use crate::matrix::{Matrix, MatrixView};
+use crate::num::{Float, Num, Zero};
+
+/// Below this many kernel taps, the direct sum always wins.
+const FFT_MIN: usize = 64;
+
+/// # ConvolveMode
+/// How much of the result to keep, for
+/// inputs of length `n` (first argument)
+/// and `m`:
+///
+/// - `Full`: every shift with any overlap,
+///   `n + m - 1` elements.
+/// - `Same`: `n` elements, centred on the full
+///   result, so the output lines up with the
+///   signal.
+/// - `Valid`: only shifts where one input
+///   covers the other completely, `max - min + 1`
+///   elements.
+///
+/// 2D works the same way, one axis at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ConvolveMode {
+    #[default]
+    Full,
+    Same,
+    Valid,
+}
+
+impl ConvolveMode {
+    /// Where the kept part starts in the full result, and how long it is.
+    fn window(self, n: usize, m: usize) -> (usize, usize) {
+        if n == 0 || m == 0 {
+            return (0, 0);
+        }
+        match self {
+            ConvolveMode::Full => (0, n + m - 1),
+            ConvolveMode::Same => ((m - 1) / 2, n),
+            ConvolveMode::Valid => (n.min(m) - 1, n.max(m) - n.min(m) + 1),
+        }
+    }
+}
+
+/// Element `k` of the full convolution of `a` with
+/// the kernel whose reverse is `r`.
+fn full_at<T: Num>(a: &[T], r: &[T], k: usize) -> T {
+    let m = r.len();
+    let lo = (k + 1).saturating_sub(m);
+    let hi = k.min(a.len() - 1);
+    dot_slice(&a[lo..=hi], &r[m - 1 + lo - k..])
+}
+
+fn slide<T: Num>(a: &[T], r: &[T], mode: ConvolveMode) -> Vec<T> {
+    let (start, len) = mode.window(a.len(), r.len());
+    (start..start + len).map(|k| full_at(a, r, k)).collect()
+}
+
+/// The whole `n + m - 1` convolution, through one
+/// power-of-two real FFT size.
+fn fft_full<T: Float>(a: &[T], v: &[T]) -> Vec<T> {
+    let len = a.len() + v.len() - 1;
+    let size = len.next_power_of_two();
+    let plan = RealFftPlan::new(size);
+    let spectrum = |x: &[T]| {
+        let mut buf = vec![T::ZERO; size];
+        buf[..x.len()].copy_from_slice(x);
+        let mut out = vec![Complex::ZERO; plan.spectrum_len()];
+        plan.forward(&buf, &mut out);
+        out
+    };
+    let mut p = spectrum(a);
+    for (x, &y) in p.iter_mut().zip(&spectrum(v)) {
+        *x = *x * y;
+    }
+    let mut out = vec![T::ZERO; size];
+    plan.inverse(&p, &mut out);
+    out.truncate(len);
+    out
+}
+
+fn use_fft(n: usize, m: usize, mode: ConvolveMode) -> bool {
+    let short = n.min(m);
+    if short < FFT_MIN {
+        return false;
+    }
+    let size = (n + m - 1).next_power_of_two();
+    let log = size.trailing_zeros() as usize;
+    // Direct is one multiply-add per tap per output; the FFT route
+    // is three real transforms, roughly 6·size·log2(size) flops.
+    mode.window(n, m).1 * short > 6 * size * log
+}
+
+/// # convolve_direct()
+/// Takes references to a signal and a kernel,
+/// and a `ConvolveMode`.
+///
+/// Returns their convolution as a new shiny
+/// Vec\<T\>, worked out as a sliding `dot_slice()`,
+/// so it's exact for integers. Empty input gives
+/// an empty result.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::convolve::{convolve_direct, ConvolveMode};
+///
+/// let q = convolve_direct(&[1, 2, 3], &[1, 1], ConvolveMode::Full);
+/// assert_eq!(vec![1, 3, 5, 3], q);
+/// let q = convolve_direct(&[1, 2, 3], &[1, 1], ConvolveMode::Valid);
+/// assert_eq!(vec![3, 5], q);
+/// ```
+pub fn convolve_direct<T: Num>(a: &[T], v: &[T], mode: ConvolveMode) -> Vec<T> {
+    let r: Vec<T> = v.iter().rev().copied().collect();
+    slide(a, &r, mode)
+}
+
+/// # convolve_fft()
+/// Takes references to a signal and a kernel,
+/// and a `ConvolveMode`.
+///
+/// Returns their convolution as a new shiny
+/// Vec\<T\>, worked out by multiplying spectra.
+/// `O((n + m) log(n + m))` instead of `O(n·m)`,
+/// at the price of rounding error on the order
+/// of `ε` times the largest output.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::convolve::{convolve_fft, ConvolveMode};
+///
+/// let q = convolve_fft(&[1.0f64, 2.0, 3.0], &[0.0, 1.0, 0.5], ConvolveMode::Same);
+/// assert!(q.iter().zip(&[1.0, 2.5, 4.0]).all(|(a, b)| (a - b).abs() < 1e-12));
+/// ```
+pub fn convolve_fft<T: Float>(a: &[T], v: &[T], mode: ConvolveMode) -> Vec<T> {
+    let (start, len) = mode.window(a.len(), v.len());
+    if len == 0 {
+        return Vec::new();
+    }
+    fft_full(a, v)[start..start + len].to_vec()
+}
+
+/// # convolve()
+/// Takes references to a signal and a kernel,
+/// and a `ConvolveMode`.
+///
+/// Returns their convolution as a new shiny
+/// Vec\<T\>, like NumPy's `convolve()`. Short
+/// kernels go through `convolve_direct()`, long
+/// ones through `convolve_fft()`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::convolve::{convolve, ConvolveMode};
+///
+/// let q = convolve(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5], ConvolveMode::Full);
+/// assert_eq!(vec![0.0, 1.0, 2.5, 4.0, 1.5], q);
+/// ```
+pub fn convolve<T: Float>(a: &[T], v: &[T], mode: ConvolveMode) -> Vec<T> {
+    if use_fft(a.len(), v.len(), mode) {
+        convolve_fft(a, v, mode)
+    } else {
+        convolve_direct(a, v, mode)
+    }
+}
+
+/// # correlate_direct()
+/// Takes references to a signal and a template,
+/// and a `ConvolveMode`.
+///
+/// Returns their cross-correlation,
+/// `c[k] = Σ a[j + k]·v[j]`, as a new shiny
+/// Vec\<T\>: a convolution with `v` reversed.
+/// Nothing gets conjugated, so for complex
+/// input conjugate `v` yourself first.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::convolve::{correlate_direct, ConvolveMode};
+///
+/// let q = correlate_direct(&[1, 2, 3, 4], &[1, 2], ConvolveMode::Valid);
+/// assert_eq!(vec![5, 8, 11], q);
+/// ```
+pub fn correlate_direct<T: Num>(a: &[T], v: &[T], mode: ConvolveMode) -> Vec<T> {
+    slide(a, v, mode)
+}
+
+/// # correlate_fft()
+/// Same as `correlate_direct()`, worked out
+/// by multiplying spectra like `convolve_fft()`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::convolve::{correlate_fft, ConvolveMode};
+///
+/// let q = correlate_fft(&[1.0f64, 2.0, 3.0, 4.0], &[1.0, 2.0], ConvolveMode::Valid);
+/// assert!(q.iter().zip(&[5.0, 8.0, 11.0]).all(|(a, b)| (a - b).abs() < 1e-12));
+/// ```
+pub fn correlate_fft<T: Float>(a: &[T], v: &[T], mode: ConvolveMode) -> Vec<T> {
+    let r: Vec<T> = v.iter().rev().copied().collect();
+    convolve_fft(a, &r, mode)
+}
+
+/// # correlate()
+/// Takes references to a signal and a template,
+/// and a `ConvolveMode`.
+///
+/// Returns their cross-correlation as a new
+/// shiny Vec\<T\>, like NumPy's `correlate()`
+/// for real input, picking the direct or FFT
+/// route like `convolve()` does.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::convolve::{correlate, ConvolveMode};
+///
+/// let q = correlate(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5], ConvolveMode::Full);
+/// assert_eq!(vec![0.5, 2.0, 3.5, 3.0, 0.0], q);
+/// ```
+pub fn correlate<T: Float>(a: &[T], v: &[T], mode: ConvolveMode) -> Vec<T> {
+    if use_fft(a.len(), v.len(), mode) {
+        correlate_fft(a, v, mode)
+    } else {
+        correlate_direct(a, v, mode)
+    }
+}
+
+/// 2D `slide()`: `r` is the kernel, already flipped
+/// if it's a convolution, row-major `kr × kc`.
+fn slide2d<T: Num>(
+    image: &MatrixView<'_, T>,
+    r: &[T],
+    (kr, kc): (usize, usize),
+    mode: ConvolveMode,
+) -> Matrix<T> {
+    let (ir, ic) = image.shape();
+    let img = image.to_vec();
+    let (r0, rows) = mode.window(ir, kr);
+    let (c0, cols) = mode.window(ic, kc);
+    Matrix::from_fn(rows, cols, |i, j| {
+        let (i, j) = (i + r0, j + c0);
+        let (q_lo, q_hi) = ((j + 1).saturating_sub(kc), j.min(ic - 1));
+        ((i + 1).saturating_sub(kr)..=i.min(ir - 1)).fold(T::ZERO, |acc, p| {
+            let k = kr - 1 + p - i;
+            let row = &img[p * ic..(p + 1) * ic];
+            let krow = &r[k * kc..(k + 1) * kc];
+            acc + dot_slice(&row[q_lo..=q_hi], &krow[kc - 1 + q_lo - j..])
+        })
+    })
+}
+
+/// # convolve2d()
+/// Takes matrix views of an image and a kernel,
+/// and a `ConvolveMode`.
+///
+/// Returns their 2D convolution as a new
+/// `Matrix`, like SciPy's `convolve2d()` with
+/// zero padding. `Same` gives back the image's
+/// shape. Worked out directly, which is what you
+/// want for the small kernels images get.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::convolve::{convolve2d, ConvolveMode};
+/// use slicenator::matrix::MatrixView;
+///
+/// let img = MatrixView::row_major(&[1, 2, 3, 4, 5, 6, 7, 8, 9], 3, 3).unwrap();
+/// let k = MatrixView::row_major(&[1, 0, 0, -1], 2, 2).unwrap();
+/// let q = convolve2d(&img, &k, ConvolveMode::Same);
+/// assert_eq!(&[1, 2, 3, 4, 4, 4, 7, 4, 4], q.as_slice());
+/// ```
+pub fn convolve2d<T: Num>(
+    image: &MatrixView<'_, T>,
+    kernel: &MatrixView<'_, T>,
+    mode: ConvolveMode,
+) -> Matrix<T> {
+    // Reversing the row-major elements flips both axes.
+    let flipped: Vec<T> = kernel.to_vec().into_iter().rev().collect();
+    slide2d(image, &flipped, kernel.shape(), mode)
+}
+
+/// # correlate2d()
+/// Same as `convolve2d()` with the kernel
+/// not flipped: each output is the kernel laid
+/// over the image and dotted with it.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::convolve::{correlate2d, ConvolveMode};
+/// use slicenator::matrix::MatrixView;
+///
+/// let img = MatrixView::row_major(&[1, 2, 3, 4, 5, 6, 7, 8, 9], 3, 3).unwrap();
+/// let k = MatrixView::row_major(&[1, 0, 0, -1], 2, 2).unwrap();
+/// let q = correlate2d(&img, &k, ConvolveMode::Valid);
+/// assert_eq!(&[-4, -4, -4, -4], q.as_slice());
+/// ```
+pub fn correlate2d<T: Num>(
+    image: &MatrixView<'_, T>,
+    kernel: &MatrixView<'_, T>,
+    mode: ConvolveMode,
+) -> Matrix<T> {
+    slide2d(image, &kernel.to_vec(), kernel.shape(), mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::close;
+
+    #[test]
+    fn modes_check() {
+        let a = [1.0, 2.0, 3.0];
+        let v = [0.0, 1.0, 0.5];
+        assert_eq!(
+            vec![0.0, 1.0, 2.5, 4.0, 1.5],
+            convolve(&a, &v, ConvolveMode::Full)
+        );
+        assert_eq!(vec![1.0, 2.5, 4.0], convolve(&a, &v, ConvolveMode::Same));
+        assert_eq!(vec![2.5], convolve(&a, &v, ConvolveMode::Valid));
+        assert_eq!(vec![3.5], correlate(&a, &v, ConvolveMode::Valid));
+        // Valid doesn't care which one is longer.
+        assert_eq!(
+            convolve_direct(&[1, 2], &[1, 2, 3, 4], ConvolveMode::Valid),
+            convolve_direct(&[1, 2, 3, 4], &[1, 2], ConvolveMode::Valid)
+        );
+        assert_eq!(
+            vec![1, 3, 5],
+            convolve_direct(&[1, 2, 3], &[1, 1], ConvolveMode::Same)
+        );
+        assert_eq!(
+            2,
+            convolve_direct(&[1, 2], &[1, 2, 3, 4, 5], ConvolveMode::Same).len()
+        );
+        assert!(convolve_direct::<i32>(&[], &[1, 2], ConvolveMode::Full).is_empty());
+        assert!(convolve_fft::<f64>(&[1.0], &[], ConvolveMode::Same).is_empty());
+    }
+
+    #[test]
+    fn fft_check() {
+        let a: Vec<f64> = (0..300)
+            .map(|i| ((i * 7 % 13) as f64 - 6.0) / 3.0)
+            .collect();
+        let v: Vec<f64> = (0..100).map(|i| ((i * 5 % 11) as f64) / 10.0).collect();
+        assert!(use_fft(a.len(), v.len(), ConvolveMode::Full));
+        for mode in [ConvolveMode::Full, ConvolveMode::Same, ConvolveMode::Valid] {
+            let d = convolve_direct(&a, &v, mode);
+            assert!(close(&d, &convolve(&a, &v, mode), 1e-9));
+            assert!(close(&d, &convolve_fft(&a, &v, mode), 1e-9));
+            let c = correlate_direct(&a, &v, mode);
+            assert!(close(&c, &correlate(&a, &v, mode), 1e-9));
+        }
+        let f = convolve_fft(&[1.0f32, 2.0, 3.0], &[1.0, 1.0], ConvolveMode::Full);
+        assert!(f
+            .iter()
+            .zip(&[1.0, 3.0, 5.0, 3.0])
+            .all(|(x, y)| (x - y).abs() < 1e-5));
+    }
+
+    #[test]
+    fn conv2d_check() {
+        let img = MatrixView::row_major(&[1, 2, 3, 4, 5, 6, 7, 8, 9], 3, 3).unwrap();
+        let k = MatrixView::row_major(&[1, 0, 0, -1], 2, 2).unwrap();
+        let full = convolve2d(&img, &k, ConvolveMode::Full);
+        assert_eq!((4, 4), full.shape());
+        assert_eq!(
+            &[1, 2, 3, 0, 4, 4, 4, -3, 7, 4, 4, -6, 0, -7, -8, -9],
+            full.as_slice()
+        );
+        assert_eq!(
+            &[4, 4, 4, 4],
+            convolve2d(&img, &k, ConvolveMode::Valid).as_slice()
+        );
+        let c = correlate2d(&img, &k, ConvolveMode::Full);
+        assert!(c
+            .as_slice()
+            .iter()
+            .zip(full.as_slice())
+            .all(|(x, y)| *x == -y));
+
+        // A transposed view goes through the same as a copy would.
+        let t = img.transpose();
+        let owned = t.to_vec();
+        let tv = MatrixView::row_major(&owned, 3, 3).unwrap();
+        assert_eq!(
+            convolve2d(&tv, &k, ConvolveMode::Same),
+            convolve2d(&t, &k, ConvolveMode::Same)
+        );
+
+        // One row each way is the 1D convolution.
+        let a = MatrixView::row_major(&[1, 2, 3], 1, 3).unwrap();
+        let v = MatrixView::row_major(&[1, 1], 1, 2).unwrap();
+        assert_eq!(
+            &convolve_direct(&[1, 2, 3], &[1, 1], ConvolveMode::Full)[..],
+            convolve2d(&a, &v, ConvolveMode::Full).as_slice()
+        );
+    }
+}
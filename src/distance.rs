@@ -0,0 +1,289 @@
+//! Distances and similarities between a couple of slices.
+//!
+//! Same rule as `dot_slice()`: sizes don't have to match, only the
+//! shortest slice counts. Manhattan, Chebyshev and Hamming take any
+//! `Num` (integers too, unsigned included); the rest need a `Float`.
+
+use crate::norm;
+use crate::norm::{max_of, scaled_sum_pow, scaled_sum_sq};
+use crate::num::{Float, Num};
+
+fn diffs<'a, T: Float>(a: &'a [T], b: &'a [T]) -> impl Iterator<Item = T> + 'a {
+    a.iter().zip(b).map(|(&x, &y)| x - y)
+}
+
+/// `|x - y|` without going below zero on the way, so unsigned
+/// types are fine. NaN in, NaN out.
+fn abs_diffs<'a, T: Num + PartialOrd>(a: &'a [T], b: &'a [T]) -> impl Iterator<Item = T> + 'a {
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| if x < y { y - x } else { x - y })
+}
+
+/// # euclidean_distance()
+/// Takes a reference to a couple of float slices.
+///
+/// Returns the straight line distance between
+/// them, scaled so it won't overflow.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::distance::euclidean_distance;
+///
+/// assert_eq!(5.0, euclidean_distance(&[1.0, 1.0], &[4.0, 5.0]));
+/// ```
+pub fn euclidean_distance<T: Float>(a: &[T], b: &[T]) -> T {
+    let (scale, ssq) = scaled_sum_sq(diffs(a, b));
+    scale * ssq.sqrt()
+}
+
+/// # sq_euclidean_distance()
+/// Takes a reference to a couple of float slices.
+///
+/// Returns the squared straight line distance.
+/// Cheaper than `euclidean_distance()` when you
+/// only need to compare.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::distance::sq_euclidean_distance;
+///
+/// assert_eq!(25.0, sq_euclidean_distance(&[1.0, 1.0], &[4.0, 5.0]));
+/// ```
+pub fn sq_euclidean_distance<T: Float>(a: &[T], b: &[T]) -> T {
+    diffs(a, b).fold(T::ZERO, |acc, d| acc + d * d)
+}
+
+/// # manhattan_distance()
+/// Takes a reference to a couple of slices of
+/// numbers.
+///
+/// Returns the sum of absolute differences.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::distance::manhattan_distance;
+///
+/// assert_eq!(7.0, manhattan_distance(&[1.0, 1.0], &[4.0, 5.0]));
+/// assert_eq!(7u8, manhattan_distance(&[1, 5], &[4, 1]));
+/// ```
+pub fn manhattan_distance<T: Num + PartialOrd>(a: &[T], b: &[T]) -> T {
+    abs_diffs(a, b).fold(T::ZERO, |acc, d| acc + d)
+}
+
+/// # chebyshev_distance()
+/// Takes a reference to a couple of slices of
+/// numbers.
+///
+/// Returns the biggest absolute difference.
+/// Any NaN makes it NaN.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::distance::chebyshev_distance;
+///
+/// assert_eq!(4.0, chebyshev_distance(&[1.0, 1.0], &[4.0, 5.0]));
+/// assert_eq!(4u8, chebyshev_distance(&[1, 5], &[4, 1]));
+/// ```
+pub fn chebyshev_distance<T: Num + PartialOrd>(a: &[T], b: &[T]) -> T {
+    max_of(abs_diffs(a, b))
+}
+
+/// # minkowski_distance()
+/// Takes a reference to a couple of float slices
+/// and a `p`.
+///
+/// Returns the Lp norm of their difference: p = 1
+/// is Manhattan, p = 2 Euclidean, p = infinity
+/// Chebyshev. `p` of zero or less is NaN.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::distance::minkowski_distance;
+///
+/// assert_eq!(5.0, minkowski_distance(&[1.0, 1.0], &[4.0, 5.0], 2.0));
+/// ```
+pub fn minkowski_distance<T: Float>(a: &[T], b: &[T], p: T) -> T {
+    if p.is_nan() || p <= T::ZERO {
+        return T::NAN;
+    }
+    if p == T::INFINITY {
+        return chebyshev_distance(a, b);
+    }
+    if p == T::ONE {
+        return manhattan_distance(a, b);
+    }
+    if p == T::ONE + T::ONE {
+        return euclidean_distance(a, b);
+    }
+    let (scale, ssq) = scaled_sum_pow(diffs(a, b), p);
+    scale * ssq.powf(T::ONE / p)
+}
+
+/// # cosine_similarity()
+/// Takes a reference to a couple of float slices.
+///
+/// Returns the cosine of the angle between them,
+/// from -1 (opposite) through 0 (at right angles)
+/// to 1 (same direction). `None` if either one is
+/// all zeros. Both get scaled down by their
+/// biggest value first, so huge values won't
+/// overflow.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::distance::cosine_similarity;
+///
+/// assert_eq!(Some(0.0), cosine_similarity(&[1.0, 0.0], &[0.0, 3.0]));
+/// assert_eq!(Some(-1.0), cosine_similarity(&[1.0, 2.0], &[-2.0, -4.0]));
+/// ```
+pub fn cosine_similarity<T: Float>(a: &[T], b: &[T]) -> Option<T> {
+    let len = a.len().min(b.len());
+    let (a, b) = (&a[..len], &b[..len]);
+    // Everything gets divided by the biggest |x| on its side first, so
+    // neither the squares nor the products can overflow.
+    let sa = norm::linf_norm(a);
+    let sb = norm::linf_norm(b);
+    if sa == T::ZERO || sb == T::ZERO {
+        return None;
+    }
+    let (mut ab, mut aa, mut bb) = (T::ZERO, T::ZERO, T::ZERO);
+    for (&x, &y) in a.iter().zip(b) {
+        let (x, y) = (x / sa, y / sb);
+        ab = ab + x * y;
+        aa = aa + x * x;
+        bb = bb + y * y;
+    }
+    let c = ab / (aa * bb).sqrt();
+    let one = T::ONE;
+    Some(if c > one {
+        one
+    } else if c < -one {
+        -one
+    } else {
+        c
+    })
+}
+
+/// # cosine_distance()
+/// Takes a reference to a couple of float slices.
+///
+/// Returns one minus `cosine_similarity()`, so
+/// 0 for the same direction and 2 for opposite.
+/// `None` if either one is all zeros.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::distance::cosine_distance;
+///
+/// assert_eq!(Some(1.0), cosine_distance(&[1.0, 0.0], &[0.0, 3.0]));
+/// ```
+pub fn cosine_distance<T: Float>(a: &[T], b: &[T]) -> Option<T> {
+    cosine_similarity(a, b).map(|c| T::ONE - c)
+}
+
+/// # hamming_distance()
+/// Takes a reference to a couple of slices of
+/// anything comparable.
+///
+/// Returns how many positions hold different
+/// values.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::distance::hamming_distance;
+///
+/// assert_eq!(3, hamming_distance(b"karolin", b"kathrin"));
+/// ```
+pub fn hamming_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    a.iter().zip(b).filter(|(x, y)| x != y).count()
+}
+
+/// # jaccard_distance()
+/// Takes a reference to a couple of non-negative
+/// float slices.
+///
+/// Returns the (weighted) Jaccard distance,
+/// `1 - Σ min / Σ max`. With 0/1 values that's the
+/// usual set version. `None` if both are all zeros.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::distance::jaccard_distance;
+///
+/// let t = [1.0, 1.0, 0.0, 1.0];
+/// let u = [1.0, 0.0, 1.0, 1.0];
+/// assert_eq!(Some(0.5), jaccard_distance(&t, &u));
+/// ```
+pub fn jaccard_distance<T: Float>(a: &[T], b: &[T]) -> Option<T> {
+    let (mut lo, mut hi) = (T::ZERO, T::ZERO);
+    for (&x, &y) in a.iter().zip(b) {
+        let (small, big) = if x < y { (x, y) } else { (y, x) };
+        lo = lo + small;
+        hi = hi + big;
+    }
+    if hi == T::ZERO {
+        return None;
+    }
+    Some(T::ONE - lo / hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortest_slice_check() {
+        let t = [1.0, 1.0, 100.0];
+        let u = [4.0, 5.0];
+        assert_eq!(5.0, euclidean_distance(&t, &u));
+        assert_eq!(25.0, sq_euclidean_distance(&u, &t));
+        assert_eq!(7.0, manhattan_distance(&t, &u));
+        assert_eq!(4.0, chebyshev_distance(&t, &u));
+        assert_eq!(1, hamming_distance(&[1, 2, 3], &[1, 5]));
+    }
+
+    #[test]
+    fn integer_check() {
+        let t = [3u8, 250, 0];
+        let u = [10u8, 5, 0];
+        assert_eq!(252, manhattan_distance(&t, &u));
+        assert_eq!(245, chebyshev_distance(&t, &u));
+        assert_eq!(9, manhattan_distance(&[-3i64, 4], &[2, 0]));
+        assert_eq!(0, chebyshev_distance::<i32>(&[], &[1]));
+        assert!(chebyshev_distance(&[f64::NAN], &[1.0]).is_nan());
+    }
+
+    #[test]
+    fn minkowski_check() {
+        let t = [0.0, 0.0, 0.0];
+        let u = [1.0, -2.0, 2.0];
+        assert_eq!(manhattan_distance(&t, &u), minkowski_distance(&t, &u, 1.0));
+        assert_eq!(euclidean_distance(&t, &u), minkowski_distance(&t, &u, 2.0));
+        assert_eq!(2.0, minkowski_distance(&t, &u, f64::INFINITY));
+        let q = minkowski_distance(&t, &u, 3.0);
+        assert!((q - 17f64.cbrt()).abs() < 1e-12);
+        assert_eq!(5e300, euclidean_distance(&[3e300, 0.0], &[0.0, -4e300]));
+    }
+
+    #[test]
+    fn cosine_check() {
+        assert_eq!(None, cosine_similarity(&[0.0, 0.0], &[1.0, 2.0]));
+        assert_eq!(None, cosine_distance(&[1.0], &[]));
+        let q = cosine_similarity(&[1e200, 1e200], &[1e-200, 1e-200]).unwrap();
+        assert!((q - 1.0).abs() < 1e-12);
+        let q = cosine_similarity(&[1e200, 1e200], &[1e200, -1e200]).unwrap();
+        assert!(q.abs() < 1e-12);
+        let q = cosine_similarity(&[f64::MAX, f64::MAX], &[f64::MAX, f64::MAX]).unwrap();
+        assert!((q - 1.0).abs() < 1e-12);
+        let q = cosine_distance(&[1.0f32, 2.0, 3.0], &[1.0, 2.0, 3.0]).unwrap();
+        assert!(q.abs() < 1e-6);
+    }
+
+    #[test]
+    fn jaccard_check() {
+        assert_eq!(None, jaccard_distance(&[0.0, 0.0], &[0.0, 0.0]));
+        assert_eq!(Some(0.0), jaccard_distance(&[1.0, 2.0], &[1.0, 2.0]));
+        assert_eq!(Some(0.75), jaccard_distance(&[1.0, 0.0], &[4.0, 0.0]));
+    }
+}
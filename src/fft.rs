@@ -0,0 +1,586 @@
+//! Fast Fourier transforms of slices.
+//!
+//! `FftPlan` does complex transforms of any length: iterative radix-2
+//! for powers of two, Bluestein's chirp-z trick (three power-of-two
+//! transforms) for everything else. `RealFftPlan` does real input
+//! through a complex transform of half the length. Building a plan
+//! works out the twiddle factors once, so if you're transforming lots
+//! of same-sized blocks, make a plan and keep it.
+//!
+//! `fft()`, `ifft()`, `rfft()`, `irfft()` and `power_spectrum()` are
+//! one-shot shortcuts that build a plan each call.
+//!
+//! Conventions are NumPy's: the forward transform is unscaled,
+//! `X[k] = Σ x[j]·e^(-2πi·jk/n)`, and the inverse divides by `n`, so a
+//! round trip gives back what you started with.
+
+use std::f64::consts::PI;
+
+use crate::complex::Complex;
+use crate::num::{Float, One, Zero};
+
+/// `e^(-2πi·k/n)`, worked out in f64 whatever `T` is.
+fn twiddle<T: Float>(k: usize, n: usize) -> Complex<T> {
+    let a = -2.0 * PI * k as f64 / n as f64;
+    Complex::new(T::from_f64(a.cos()), T::from_f64(a.sin()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind<T> {
+    /// `n` is a power of two (or 0 or 1); `e^(-2πi·k/n)` for `k < n/2`.
+    Radix2(Vec<Complex<T>>),
+    /// Anything else, as a convolution of length `m >= 2n - 1`.
+    Bluestein {
+        chirp: Vec<Complex<T>>,
+        filter: Vec<Complex<T>>,
+        inner: Box<FftPlan<T>>,
+    },
+}
+
+/// # FftPlan
+/// A reusable complex FFT of one length, with
+/// its twiddle factors worked out up front.
+///
+/// Powers of two take the radix-2 path; other
+/// lengths go through Bluestein's algorithm,
+/// which is still `O(n log n)` but a few times
+/// slower.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::Complex;
+/// use slicenator::fft::FftPlan;
+///
+/// let plan = FftPlan::new(4);
+/// let mut buf = [1.0, 2.0, 3.0, 4.0].map(|x| Complex::new(x, 0.0));
+/// plan.forward(&mut buf);
+/// assert_eq!(Complex::new(10.0, 0.0), buf[0]);
+/// assert_eq!(Complex::new(-2.0, 2.0), buf[1]);
+/// plan.inverse(&mut buf);
+/// assert_eq!(Complex::new(3.0, 0.0), buf[2]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FftPlan<T> {
+    n: usize,
+    kind: Kind<T>,
+}
+
+impl<T: Float> FftPlan<T> {
+    /// Plans a transform of length `n`.
+    pub fn new(n: usize) -> Self {
+        if n <= 1 || n.is_power_of_two() {
+            let tw = (0..n / 2).map(|k| twiddle(k, n)).collect();
+            return FftPlan {
+                n,
+                kind: Kind::Radix2(tw),
+            };
+        }
+        let m = (2 * n - 1).next_power_of_two();
+        let inner = FftPlan::new(m);
+        // k² mod 2n keeps the angle small, and exact, for big k.
+        let chirp: Vec<Complex<T>> = (0..n)
+            .map(|k| {
+                let a = -PI * ((k * k) % (2 * n)) as f64 / n as f64;
+                Complex::new(T::from_f64(a.cos()), T::from_f64(a.sin()))
+            })
+            .collect();
+        let mut filter = vec![Complex::ZERO; m];
+        filter[0] = chirp[0].conj();
+        for k in 1..n {
+            filter[k] = chirp[k].conj();
+            filter[m - k] = chirp[k].conj();
+        }
+        inner.forward(&mut filter);
+        FftPlan {
+            n,
+            kind: Kind::Bluestein {
+                chirp,
+                filter,
+                inner: Box::new(inner),
+            },
+        }
+    }
+
+    /// The transform length.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Transforms `buf` in place, unscaled.
+    ///
+    /// Panics if `buf` isn't `len()` long.
+    pub fn forward(&self, buf: &mut [Complex<T>]) {
+        assert_eq!(
+            self.n,
+            buf.len(),
+            "buffer of {} for an FFT of {}",
+            buf.len(),
+            self.n
+        );
+        match &self.kind {
+            Kind::Radix2(tw) => radix2(buf, tw),
+            Kind::Bluestein {
+                chirp,
+                filter,
+                inner,
+            } => {
+                let mut work = vec![Complex::ZERO; filter.len()];
+                for ((w, &x), &c) in work.iter_mut().zip(buf.iter()).zip(chirp) {
+                    *w = x * c;
+                }
+                inner.forward(&mut work);
+                for (w, &f) in work.iter_mut().zip(filter) {
+                    *w = *w * f;
+                }
+                inner.inverse(&mut work);
+                for ((x, &w), &c) in buf.iter_mut().zip(&work).zip(chirp) {
+                    *x = w * c;
+                }
+            }
+        }
+    }
+
+    /// Inverse transform of `buf` in place, divided
+    /// by `len()`, so it undoes `forward()`.
+    ///
+    /// Panics if `buf` isn't `len()` long.
+    pub fn inverse(&self, buf: &mut [Complex<T>]) {
+        for z in buf.iter_mut() {
+            *z = z.conj();
+        }
+        self.forward(buf);
+        let scale = T::ONE / T::from_f64(self.n.max(1) as f64);
+        for z in buf.iter_mut() {
+            *z = z.conj() * scale;
+        }
+    }
+}
+
+/// Iterative decimation-in-time radix-2 FFT.
+fn radix2<T: Float>(buf: &mut [Complex<T>], tw: &[Complex<T>]) {
+    let n = buf.len();
+    if n <= 1 {
+        return;
+    }
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            buf.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let step = n / len;
+        for chunk in buf.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for ((a, b), &w) in lo
+                .iter_mut()
+                .zip(hi.iter_mut())
+                .zip(tw.iter().step_by(step))
+            {
+                let t = *b * w;
+                *b = *a - t;
+                *a = *a + t;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// # RealFftPlan
+/// A reusable FFT of real input of one length.
+///
+/// Only the `n/2 + 1` non-negative frequency
+/// bins come out; the rest are their complex
+/// conjugates. Even lengths pack the input into
+/// a complex transform of half the size, about
+/// twice as fast as transforming it as complex.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::Complex;
+/// use slicenator::fft::RealFftPlan;
+///
+/// let plan = RealFftPlan::new(4);
+/// let mut spec = [Complex::new(0.0, 0.0); 3];
+/// plan.forward(&[1.0, 2.0, 3.0, 4.0], &mut spec);
+/// assert_eq!([Complex::new(10.0, 0.0), Complex::new(-2.0, 2.0), Complex::new(-2.0, 0.0)], spec);
+///
+/// let mut back = [0.0; 4];
+/// plan.inverse(&spec, &mut back);
+/// assert_eq!([1.0, 2.0, 3.0, 4.0], back);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealFftPlan<T> {
+    n: usize,
+    /// Half length for even `n`, full length for odd.
+    plan: FftPlan<T>,
+    /// `e^(-2πi·k/n)` for `k < n/2`, even `n` only.
+    tw: Vec<Complex<T>>,
+}
+
+impl<T: Float> RealFftPlan<T> {
+    /// Plans a real transform of length `n`.
+    pub fn new(n: usize) -> Self {
+        if n.is_multiple_of(2) {
+            RealFftPlan {
+                n,
+                plan: FftPlan::new(n / 2),
+                tw: (0..n / 2).map(|k| twiddle(k, n)).collect(),
+            }
+        } else {
+            RealFftPlan {
+                n,
+                plan: FftPlan::new(n),
+                tw: Vec::new(),
+            }
+        }
+    }
+
+    /// The (real) transform length.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// How many bins come out: `len() / 2 + 1`.
+    pub fn spectrum_len(&self) -> usize {
+        self.n / 2 + 1
+    }
+
+    /// Transforms `input` into the first `spectrum_len()`
+    /// bins of its spectrum, unscaled.
+    ///
+    /// Panics if `input` isn't `len()` long or `out`
+    /// isn't `spectrum_len()` long.
+    pub fn forward(&self, input: &[T], out: &mut [Complex<T>]) {
+        let n = self.n;
+        assert_eq!(n, input.len(), "input of {} for an FFT of {n}", input.len());
+        assert_eq!(
+            self.spectrum_len(),
+            out.len(),
+            "spectrum of {} for an FFT of {n}",
+            out.len()
+        );
+        if n == 0 {
+            out[0] = Complex::ZERO;
+            return;
+        }
+        if !n.is_multiple_of(2) {
+            let mut buf: Vec<Complex<T>> = input.iter().map(|&x| Complex::from(x)).collect();
+            self.plan.forward(&mut buf);
+            out.copy_from_slice(&buf[..out.len()]);
+            return;
+        }
+        let h = n / 2;
+        let mut z: Vec<Complex<T>> = input
+            .chunks_exact(2)
+            .map(|p| Complex::new(p[0], p[1]))
+            .collect();
+        self.plan.forward(&mut z);
+        let half = T::from_f64(0.5);
+        for k in 0..=h {
+            let zk = z[k % h];
+            let zc = z[(h - k) % h].conj();
+            let e = (zk + zc) * half;
+            // (zk - zc) / 2i
+            let d = (zk - zc) * half;
+            let o = Complex::new(d.im, -d.re);
+            let w = if k < h { self.tw[k] } else { -Complex::ONE };
+            out[k] = e + w * o;
+        }
+    }
+
+    /// Turns `spectrum_len()` bins back into `len()`
+    /// real samples, divided by `len()`, so it undoes
+    /// `forward()`. The imaginary parts that a real
+    /// signal's spectrum can't have (bin 0, and the
+    /// last one for even lengths) are ignored.
+    ///
+    /// Panics if the lengths are wrong, as for `forward()`.
+    pub fn inverse(&self, input: &[Complex<T>], out: &mut [T]) {
+        let n = self.n;
+        assert_eq!(n, out.len(), "output of {} for an FFT of {n}", out.len());
+        assert_eq!(
+            self.spectrum_len(),
+            input.len(),
+            "spectrum of {} for an FFT of {n}",
+            input.len()
+        );
+        if n == 0 {
+            return;
+        }
+        if !n.is_multiple_of(2) {
+            let mut buf = vec![Complex::ZERO; n];
+            buf[..input.len()].copy_from_slice(input);
+            buf[0].im = T::ZERO;
+            for k in input.len()..n {
+                buf[k] = buf[n - k].conj();
+            }
+            self.plan.inverse(&mut buf);
+            for (o, z) in out.iter_mut().zip(&buf) {
+                *o = z.re;
+            }
+            return;
+        }
+        let h = n / 2;
+        let mut x = input.to_vec();
+        x[0].im = T::ZERO;
+        x[h].im = T::ZERO;
+        let half = T::from_f64(0.5);
+        let mut z: Vec<Complex<T>> = (0..h)
+            .map(|k| {
+                let xc = x[h - k].conj();
+                let e = (x[k] + xc) * half;
+                let o = (x[k] - xc) * self.tw[k].conj() * half;
+                // e + i·o
+                Complex::new(e.re - o.im, e.im + o.re)
+            })
+            .collect();
+        self.plan.inverse(&mut z);
+        for (p, z) in out.chunks_exact_mut(2).zip(&z) {
+            p[0] = z.re;
+            p[1] = z.im;
+        }
+    }
+}
+
+/// # fft()
+/// Takes a reference to a complex slice.
+///
+/// Returns its discrete Fourier transform,
+/// unscaled. Any length works.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::Complex;
+/// use slicenator::fft::fft;
+///
+/// let x = [1.0, 0.0, 0.0, 0.0].map(|re| Complex::new(re, 0.0));
+/// assert_eq!(vec![Complex::new(1.0, 0.0); 4], fft(&x));
+///
+/// let y = fft(&[Complex::new(1.0f64, 0.0); 3]);
+/// assert!((y[0].re - 3.0).abs() < 1e-12 && y[1].abs() < 1e-12);
+/// ```
+pub fn fft<T: Float>(a: &[Complex<T>]) -> Vec<Complex<T>> {
+    let mut buf = a.to_vec();
+    FftPlan::new(a.len()).forward(&mut buf);
+    buf
+}
+
+/// # ifft()
+/// Takes a reference to a complex slice.
+///
+/// Returns its inverse discrete Fourier
+/// transform, divided by the length, so
+/// `ifft(&fft(&x))` is `x` (give or take
+/// rounding).
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::Complex;
+/// use slicenator::fft::ifft;
+///
+/// let x = ifft(&[Complex::new(4.0, 0.0); 4]);
+/// assert_eq!(Complex::new(4.0, 0.0), x[0]);
+/// assert_eq!(Complex::new(0.0, 0.0), x[1]);
+/// ```
+pub fn ifft<T: Float>(a: &[Complex<T>]) -> Vec<Complex<T>> {
+    let mut buf = a.to_vec();
+    FftPlan::new(a.len()).inverse(&mut buf);
+    buf
+}
+
+/// # rfft()
+/// Takes a reference to a real slice.
+///
+/// Returns the `n/2 + 1` non-negative frequency
+/// bins of its Fourier transform, unscaled.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::complex::Complex;
+/// use slicenator::fft::rfft;
+///
+/// let q = rfft(&[1.0, 1.0, 1.0, 1.0]);
+/// assert_eq!(vec![Complex::new(4.0, 0.0), Complex::new(0.0, 0.0), Complex::new(0.0, 0.0)], q);
+/// ```
+pub fn rfft<T: Float>(a: &[T]) -> Vec<Complex<T>> {
+    let plan = RealFftPlan::new(a.len());
+    let mut out = vec![Complex::ZERO; plan.spectrum_len()];
+    plan.forward(a, &mut out);
+    out
+}
+
+/// # irfft()
+/// Takes a reference to the non-negative
+/// frequency bins of a real signal's spectrum
+/// and how many samples `n` you want back.
+///
+/// Returns the real signal, divided by `n`, so
+/// `irfft(&rfft(&x), x.len())` is `x`. Bins past
+/// `n/2` are ignored and missing ones count as
+/// zero, same as NumPy.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::fft::{irfft, rfft};
+///
+/// let x = [1.0, -2.0, 0.5, 4.0, 3.0];
+/// let y = irfft(&rfft(&x), 5);
+/// assert!(x.iter().zip(&y).all(|(a, b): (&f64, _)| (a - b).abs() < 1e-12));
+/// ```
+pub fn irfft<T: Float>(a: &[Complex<T>], n: usize) -> Vec<T> {
+    let plan = RealFftPlan::new(n);
+    let mut spec = vec![Complex::ZERO; plan.spectrum_len()];
+    let len = a.len().min(spec.len());
+    spec[..len].copy_from_slice(&a[..len]);
+    let mut out = vec![T::ZERO; n];
+    plan.inverse(&spec, &mut out);
+    out
+}
+
+/// # power_spectrum()
+/// Takes a reference to a real slice.
+///
+/// Returns `|X[k]|²` for each of the `n/2 + 1`
+/// bins of `rfft()`. Unscaled: divide by `n`
+/// for a periodogram, and see `rfft_freqs()` for
+/// which frequency each bin is.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::fft::power_spectrum;
+///
+/// let p = power_spectrum(&[1.0, -1.0, 1.0, -1.0]);
+/// assert_eq!(vec![0.0, 0.0, 16.0], p);
+/// ```
+pub fn power_spectrum<T: Float>(a: &[T]) -> Vec<T> {
+    rfft(a).into_iter().map(|z| z.norm_sqr()).collect()
+}
+
+/// # rfft_freqs()
+/// Takes a length `n` and a sample rate.
+///
+/// Returns the frequency of each of the `n/2 + 1`
+/// bins `rfft()` gives for `n` samples:
+/// `k · sample_rate / n`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::fft::rfft_freqs;
+///
+/// assert_eq!(vec![0.0, 250.0, 500.0], rfft_freqs(4, 1000.0));
+/// ```
+pub fn rfft_freqs<T: Float>(n: usize, sample_rate: T) -> Vec<T> {
+    let step = sample_rate / T::from_f64(n.max(1) as f64);
+    (0..=n / 2).map(|k| T::from_f64(k as f64) * step).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let n = x.len();
+        (0..n)
+            .map(|k| {
+                x.iter()
+                    .enumerate()
+                    .map(|(j, &v)| v * twiddle::<f64>((j * k) % n, n))
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn signal(n: usize) -> Vec<Complex<f64>> {
+        (0..n)
+            .map(|j| {
+                Complex::new(
+                    (j as f64 * 0.7).sin() + 0.1 * j as f64,
+                    (j as f64 * 1.3).cos(),
+                )
+            })
+            .collect()
+    }
+
+    fn close(a: &[Complex<f64>], b: &[Complex<f64>], tol: f64) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (*x - *y).abs() < tol)
+    }
+
+    #[test]
+    fn against_dft_check() {
+        for n in [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 17, 30, 64, 100] {
+            let x = signal(n);
+            let tol = 1e-9 * (n.max(1) as f64);
+            assert!(close(&dft(&x), &fft(&x), tol), "n = {n}");
+            assert!(close(&x, &ifft(&fft(&x)), 1e-12), "n = {n}");
+        }
+    }
+
+    #[test]
+    fn plan_reuse_check() {
+        let plan = FftPlan::new(12);
+        assert_eq!(12, plan.len());
+        for s in 0..3 {
+            let x: Vec<Complex<f64>> = signal(12 + s)[s..].to_vec();
+            let mut buf = x.clone();
+            plan.forward(&mut buf);
+            assert!(close(&dft(&x), &buf, 1e-10));
+        }
+    }
+
+    #[test]
+    fn real_check() {
+        for n in [1, 2, 3, 4, 5, 8, 9, 10, 15, 16, 100, 128] {
+            let x: Vec<f64> = signal(n).iter().map(|z| z.re).collect();
+            let full = fft(&x.iter().map(|&v| Complex::from(v)).collect::<Vec<_>>());
+            let half = rfft(&x);
+            assert_eq!(n / 2 + 1, half.len());
+            assert!(close(&full[..n / 2 + 1], &half, 1e-10), "n = {n}");
+            let back = irfft(&half, n);
+            assert!(
+                x.iter().zip(&back).all(|(a, b)| (a - b).abs() < 1e-12),
+                "n = {n}"
+            );
+        }
+        assert_eq!(vec![Complex::ZERO], rfft::<f64>(&[]));
+    }
+
+    #[test]
+    fn spectrum_check() {
+        // A 50 Hz tone sampled at 400 Hz lands in bin 8 of 64.
+        let x: Vec<f64> = (0..64)
+            .map(|j| (2.0 * PI * 50.0 * j as f64 / 400.0).cos())
+            .collect();
+        let p = power_spectrum(&x);
+        let f = rfft_freqs(64, 400.0);
+        let peak = (0..p.len()).max_by(|&a, &b| p[a].total_cmp(&p[b])).unwrap();
+        assert_eq!(50.0, f[peak]);
+        assert!((p[peak] - 32.0 * 32.0).abs() < 1e-9);
+        // Parseval: Σ|x|² = (|X0|² + 2Σ|Xk|² + |X32|²) / n.
+        let energy: f64 = x.iter().map(|v| v * v).sum();
+        let total = p[0] + 2.0 * p[1..32].iter().sum::<f64>() + p[32];
+        assert!((energy - total / 64.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn f32_check() {
+        let x: Vec<f32> = (0..24).map(|j| (j as f32 * 0.3).sin()).collect();
+        let back = irfft(&rfft(&x), 24);
+        assert!(x.iter().zip(&back).all(|(a, b)| (a - b).abs() < 1e-5));
+    }
+}
@@ -0,0 +1,633 @@
+//! Streaming digital filters.
+//!
+//! Everything here implements `Filter`: feed it samples one at a time
+//! with `step()` or a block at a time with `process()`, and it keeps
+//! whatever history it needs between calls, so a long signal can come
+//! in chunks of any size and give the same output as all at once.
+//! `reset()` forgets the history.
+//!
+//! - `Fir` runs a finite impulse response (a sliding `dot_slice()`
+//!   against the taps).
+//! - `Biquad` is one second-order IIR section in Direct Form II
+//!   transposed; `BiquadCascade` chains them, and takes SciPy's
+//!   `sos` layout.
+//! - `Sma`, `Ema` and `Wma` are the simple, exponential and linearly
+//!   weighted moving averages.
+//!
+//! `lowpass()`, `highpass()` and `bandpass()` design FIR taps by the
+//! windowed-sinc method.
+
+use crate::dot_slice;
+use crate::num::{Float, Num};
+
+mod design;
+
+pub use design::{bandpass, highpass, lowpass, DesignError};
+
+/// # Filter
+/// Anything that turns a stream of samples
+/// into another one, keeping state in between.
+pub trait Filter {
+    type Item: Copy;
+
+    /// Feeds in one sample and returns the next
+    /// output.
+    fn step(&mut self, x: Self::Item) -> Self::Item;
+
+    /// Forgets everything seen so far, back to
+    /// how it was built.
+    fn reset(&mut self);
+
+    /// Filters a block of samples into `out`,
+    /// carrying on from the last call.
+    ///
+    /// Panics if `out` isn't as long as `input`.
+    fn process(&mut self, input: &[Self::Item], out: &mut [Self::Item]) {
+        assert_eq!(
+            input.len(),
+            out.len(),
+            "output of {} for an input of {}",
+            out.len(),
+            input.len()
+        );
+        for (y, &x) in out.iter_mut().zip(input) {
+            *y = self.step(x);
+        }
+    }
+
+    /// `process()` into a new shiny Vec.
+    fn apply(&mut self, input: &[Self::Item]) -> Vec<Self::Item> {
+        input.iter().map(|&x| self.step(x)).collect()
+    }
+}
+
+/// # Fir
+/// A finite impulse response filter,
+/// `y[i] = Σ taps[j]·x[i - j]`, with the
+/// last `taps.len() - 1` inputs carried over
+/// between blocks. Works for any `Num`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::filter::{Filter, Fir};
+///
+/// let mut f = Fir::new(&[1, 1, 1]);
+/// let mut out = [0; 3];
+/// f.process(&[1, 2, 3], &mut out);
+/// assert_eq!([1, 3, 6], out);
+/// f.process(&[4, 5, 6], &mut out);
+/// assert_eq!([9, 12, 15], out);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fir<T> {
+    taps: Vec<T>,
+    /// `taps` back to front, so each output is one `dot_slice()`.
+    rev: Vec<T>,
+    /// The last `taps.len() - 1` inputs, oldest first, then
+    /// room for the block being processed.
+    buf: Vec<T>,
+}
+
+impl<T: Num> Fir<T> {
+    pub fn new(taps: &[T]) -> Self {
+        Fir {
+            taps: taps.to_vec(),
+            rev: taps.iter().rev().copied().collect(),
+            buf: vec![T::ZERO; taps.len().saturating_sub(1)],
+        }
+    }
+
+    pub fn taps(&self) -> &[T] {
+        &self.taps
+    }
+
+    /// How many samples the filter remembers.
+    fn memory(&self) -> usize {
+        self.taps.len().saturating_sub(1)
+    }
+}
+
+impl<T: Num> Filter for Fir<T> {
+    type Item = T;
+
+    fn step(&mut self, x: T) -> T {
+        let mut y = [T::ZERO];
+        self.process(&[x], &mut y);
+        y[0]
+    }
+
+    fn reset(&mut self) {
+        self.buf.clear();
+        self.buf.resize(self.memory(), T::ZERO);
+    }
+
+    fn process(&mut self, input: &[T], out: &mut [T]) {
+        assert_eq!(
+            input.len(),
+            out.len(),
+            "output of {} for an input of {}",
+            out.len(),
+            input.len()
+        );
+        let m = self.taps.len();
+        self.buf.extend_from_slice(input);
+        for (i, y) in out.iter_mut().enumerate() {
+            *y = dot_slice(&self.buf[i..i + m], &self.rev);
+        }
+        self.buf.drain(..input.len());
+    }
+
+    fn apply(&mut self, input: &[T]) -> Vec<T> {
+        let mut out = vec![T::ZERO; input.len()];
+        self.process(input, &mut out);
+        out
+    }
+}
+
+/// # Biquad
+/// One second-order IIR section,
+///
+/// `y[i] = b0·x[i] + b1·x[i-1] + b2·x[i-2]
+///        - a1·y[i-1] - a2·y[i-2]`,
+///
+/// run in Direct Form II transposed: two state
+/// values, and the best rounding behaviour of
+/// the direct forms.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::filter::{Biquad, Filter};
+///
+/// // y[i] = x[i] + 0.5·y[i-1]
+/// let mut f = Biquad::new([1.0, 0.0, 0.0], [1.0, -0.5, 0.0]);
+/// assert_eq!(vec![1.0, 0.5, 0.25, 0.125], f.apply(&[1.0, 0.0, 0.0, 0.0]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Biquad<T> {
+    b: [T; 3],
+    /// `a1` and `a2`; `a0` is divided out.
+    a: [T; 2],
+    s: [T; 2],
+}
+
+impl<T: Float> Biquad<T> {
+    /// Feedforward `b` and feedback `a`
+    /// coefficients, `a[0]` first. Everything gets
+    /// divided by `a[0]`, so it can't be zero.
+    ///
+    /// Panics if `a[0]` is zero.
+    pub fn new(b: [T; 3], a: [T; 3]) -> Self {
+        let a0 = a[0];
+        assert!(a0 != T::ZERO, "biquad a[0] can't be zero");
+        Biquad {
+            b: [b[0] / a0, b[1] / a0, b[2] / a0],
+            a: [a[1] / a0, a[2] / a0],
+            s: [T::ZERO; 2],
+        }
+    }
+
+    /// `b` and `a` as `new()` takes them, with
+    /// `a[0]` normalised to one.
+    pub fn coefficients(&self) -> ([T; 3], [T; 3]) {
+        (self.b, [T::ONE, self.a[0], self.a[1]])
+    }
+}
+
+impl<T: Float> Filter for Biquad<T> {
+    type Item = T;
+
+    fn step(&mut self, x: T) -> T {
+        let [b0, b1, b2] = self.b;
+        let [a1, a2] = self.a;
+        let y = b0 * x + self.s[0];
+        self.s[0] = b1 * x - a1 * y + self.s[1];
+        self.s[1] = b2 * x - a2 * y;
+        y
+    }
+
+    fn reset(&mut self) {
+        self.s = [T::ZERO; 2];
+    }
+}
+
+/// # BiquadCascade
+/// Biquads run one after another, the usual
+/// way to build a higher-order IIR filter
+/// without the numerical trouble one big
+/// polynomial would have.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::filter::{BiquadCascade, Filter};
+///
+/// // Two one-pole sections, in SciPy's sos layout.
+/// let mut f = BiquadCascade::from_sos(&[
+///     [1.0, 0.0, 0.0, 1.0, -0.5, 0.0],
+///     [1.0, 0.0, 0.0, 1.0, -0.5, 0.0],
+/// ]);
+/// assert_eq!(vec![1.0, 1.0, 0.75], f.apply(&[1.0, 0.0, 0.0]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BiquadCascade<T> {
+    stages: Vec<Biquad<T>>,
+}
+
+impl<T: Float> BiquadCascade<T> {
+    pub fn new(stages: Vec<Biquad<T>>) -> Self {
+        BiquadCascade { stages }
+    }
+
+    /// One section per row, `[b0, b1, b2, a0, a1, a2]`,
+    /// which is what SciPy's `sos` filters look like.
+    ///
+    /// Panics if any row's `a0` is zero.
+    pub fn from_sos(sos: &[[T; 6]]) -> Self {
+        BiquadCascade {
+            stages: sos
+                .iter()
+                .map(|r| Biquad::new([r[0], r[1], r[2]], [r[3], r[4], r[5]]))
+                .collect(),
+        }
+    }
+
+    pub fn stages(&self) -> &[Biquad<T>] {
+        &self.stages
+    }
+}
+
+impl<T: Float> Filter for BiquadCascade<T> {
+    type Item = T;
+
+    fn step(&mut self, x: T) -> T {
+        self.stages.iter_mut().fold(x, |x, s| s.step(x))
+    }
+
+    fn reset(&mut self) {
+        self.stages.iter_mut().for_each(Biquad::reset);
+    }
+
+    fn process(&mut self, input: &[T], out: &mut [T]) {
+        assert_eq!(
+            input.len(),
+            out.len(),
+            "output of {} for an input of {}",
+            out.len(),
+            input.len()
+        );
+        out.copy_from_slice(input);
+        // A stage at a time, so each one's state stays put.
+        for s in &mut self.stages {
+            for y in out.iter_mut() {
+                *y = s.step(*y);
+            }
+        }
+    }
+}
+
+/// The last `window` samples, oldest at `pos` once it's full.
+#[derive(Debug, Clone, PartialEq)]
+struct Ring<T> {
+    data: Vec<T>,
+    window: usize,
+    pos: usize,
+}
+
+impl<T: Copy> Ring<T> {
+    fn new(window: usize) -> Self {
+        assert!(window > 0, "moving average over a window of 0");
+        Ring {
+            data: Vec::with_capacity(window),
+            window,
+            pos: 0,
+        }
+    }
+
+    /// Adds `x`, returning the sample it pushed out, if any.
+    fn push(&mut self, x: T) -> Option<T> {
+        if self.data.len() < self.window {
+            self.data.push(x);
+            return None;
+        }
+        let old = std::mem::replace(&mut self.data[self.pos], x);
+        self.pos = (self.pos + 1) % self.window;
+        Some(old)
+    }
+
+    /// Oldest first.
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        let (newer, older) = self.data.split_at(self.pos);
+        older.iter().chain(newer)
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.pos = 0;
+    }
+}
+
+/// # Sma
+/// Simple moving average: the mean of the last
+/// `window` samples, or of all of them until
+/// there are that many.
+///
+/// Keeps a running sum, re-added from scratch
+/// once per lap of the window so rounding
+/// doesn't pile up on long streams.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::filter::{Filter, Sma};
+///
+/// let mut f = Sma::new(2);
+/// assert_eq!(vec![1.0, 1.5, 2.5, 3.5], f.apply(&[1.0, 2.0, 3.0, 4.0]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sma<T> {
+    ring: Ring<T>,
+    sum: T,
+}
+
+impl<T: Float> Sma<T> {
+    /// Panics if `window` is 0.
+    pub fn new(window: usize) -> Self {
+        Sma {
+            ring: Ring::new(window),
+            sum: T::ZERO,
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.ring.window
+    }
+}
+
+impl<T: Float> Filter for Sma<T> {
+    type Item = T;
+
+    fn step(&mut self, x: T) -> T {
+        match self.ring.push(x) {
+            Some(_) if self.ring.pos == 0 => self.sum = self.ring.data.iter().sum(),
+            Some(old) => self.sum = self.sum - old + x,
+            None => self.sum = self.sum + x,
+        }
+        self.sum / T::from_f64(self.ring.data.len() as f64)
+    }
+
+    fn reset(&mut self) {
+        self.ring.clear();
+        self.sum = T::ZERO;
+    }
+}
+
+/// # Ema
+/// Exponential moving average,
+/// `y[i] = y[i-1] + α·(x[i] - y[i-1])`,
+/// starting from the first sample.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::filter::{Ema, Filter};
+///
+/// let mut f = Ema::new(0.5);
+/// assert_eq!(vec![4.0, 2.0, 1.0], f.apply(&[4.0, 0.0, 0.0]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ema<T> {
+    alpha: T,
+    value: Option<T>,
+}
+
+impl<T: Float> Ema<T> {
+    /// Smoothing factor `alpha` in `(0, 1]`; the
+    /// bigger, the faster it follows the input.
+    ///
+    /// Panics if `alpha` is out of range.
+    pub fn new(alpha: T) -> Self {
+        assert!(
+            alpha > T::ZERO && alpha <= T::ONE,
+            "EMA smoothing factor has to be in (0, 1]"
+        );
+        Ema { alpha, value: None }
+    }
+
+    /// `alpha = 2 / (span + 1)`, the pandas way of
+    /// saying "about `span` samples' worth".
+    pub fn with_span(span: usize) -> Self {
+        Ema::new(T::from_f64(2.0 / (span as f64 + 1.0)))
+    }
+
+    pub fn alpha(&self) -> T {
+        self.alpha
+    }
+
+    /// The current average, if anything went in yet.
+    pub fn value(&self) -> Option<T> {
+        self.value
+    }
+}
+
+impl<T: Float> Filter for Ema<T> {
+    type Item = T;
+
+    fn step(&mut self, x: T) -> T {
+        let y = match self.value {
+            Some(v) => self.alpha.mul_add(x - v, v),
+            None => x,
+        };
+        self.value = Some(y);
+        y
+    }
+
+    fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+/// # Wma
+/// Linearly weighted moving average over the
+/// last `window` samples: the newest counts
+/// `window` times, the oldest once. Until the
+/// window fills up, the same over however many
+/// there are.
+///
+/// O(1) a sample, re-summed once per lap like
+/// `Sma`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::filter::{Filter, Wma};
+///
+/// let mut f = Wma::new(3);
+/// let y = f.apply(&[3.0, 6.0, 9.0, 3.0]);
+/// // (1·3 + 2·6 + 3·9) / 6 and (1·6 + 2·9 + 3·3) / 6
+/// assert_eq!(vec![3.0, 5.0, 7.0, 5.5], y);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wma<T> {
+    ring: Ring<T>,
+    sum: T,
+    /// `Σ (k + 1)·x_k`, oldest `k = 0`.
+    weighted: T,
+}
+
+impl<T: Float> Wma<T> {
+    /// Panics if `window` is 0.
+    pub fn new(window: usize) -> Self {
+        Wma {
+            ring: Ring::new(window),
+            sum: T::ZERO,
+            weighted: T::ZERO,
+        }
+    }
+
+    pub fn window(&self) -> usize {
+        self.ring.window
+    }
+}
+
+impl<T: Float> Filter for Wma<T> {
+    type Item = T;
+
+    fn step(&mut self, x: T) -> T {
+        let n = T::from_f64(self.ring.window as f64);
+        match self.ring.push(x) {
+            Some(_) if self.ring.pos == 0 => {
+                let (sum, weighted) = self
+                    .ring
+                    .iter()
+                    .enumerate()
+                    .fold((T::ZERO, T::ZERO), |(s, w), (k, &v)| {
+                        (s + v, w + T::from_f64((k + 1) as f64) * v)
+                    });
+                self.sum = sum;
+                self.weighted = weighted;
+            }
+            // Everything slides down a weight, the oldest to nothing.
+            Some(old) => {
+                self.weighted = self.weighted - self.sum + n * x;
+                self.sum = self.sum - old + x;
+            }
+            None => {
+                let k = T::from_f64(self.ring.data.len() as f64);
+                self.weighted = self.weighted + k * x;
+                self.sum = self.sum + x;
+            }
+        }
+        let k = self.ring.data.len() as f64;
+        self.weighted / T::from_f64(k * (k + 1.0) / 2.0)
+    }
+
+    fn reset(&mut self) {
+        self.ring.clear();
+        self.sum = T::ZERO;
+        self.weighted = T::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convolve::{convolve_direct, ConvolveMode};
+
+    #[test]
+    fn fir_check() {
+        let taps = [3, -1, 4, 1, -5];
+        let x: Vec<i64> = (0..23).map(|i| (i * 7 % 11) - 5).collect();
+        let want = &convolve_direct(&x, &taps, ConvolveMode::Full)[..x.len()];
+
+        // Any chunking gives the same thing.
+        let mut f = Fir::new(&taps);
+        let mut got = Vec::new();
+        for c in x.chunks(4) {
+            got.extend(f.apply(c));
+        }
+        assert_eq!(want, &got[..]);
+        f.reset();
+        let one: Vec<_> = x.iter().map(|&s| f.step(s)).collect();
+        assert_eq!(want, &one[..]);
+
+        let mut empty = Fir::<f64>::new(&[]);
+        assert_eq!(vec![0.0, 0.0], empty.apply(&[1.0, 2.0]));
+        assert_eq!(vec![2.0, 4.0], Fir::new(&[2.0]).apply(&[1.0, 2.0]));
+    }
+
+    #[test]
+    fn biquad_check() {
+        let b = [0.2, 0.3, 0.1];
+        let a = [2.0, -0.6, 0.4];
+        let x: Vec<f64> = (0..40).map(|i| ((i * 5 % 9) as f64) - 4.0).collect();
+
+        // Straight off the difference equation, a0 = 2.
+        let mut want = vec![0.0; x.len()];
+        for i in 0..x.len() {
+            let xi = |d: usize| if i >= d { x[i - d] } else { 0.0 };
+            let yi = |d: usize| if i >= d { want[i - d] } else { 0.0 };
+            want[i] =
+                (b[0] * xi(0) + b[1] * xi(1) + b[2] * xi(2) - a[1] * yi(1) - a[2] * yi(2)) / a[0];
+        }
+        let mut f = Biquad::new(b, a);
+        let mut got = vec![0.0; x.len()];
+        let (l, r) = got.split_at_mut(17);
+        f.process(&x[..17], l);
+        f.process(&x[17..], r);
+        assert!(got.iter().zip(&want).all(|(g, w)| (g - w).abs() < 1e-12));
+        assert_eq!(([0.1, 0.15, 0.05], [1.0, -0.3, 0.2]), f.coefficients());
+
+        // Cascading twice is filtering twice.
+        let mut c = BiquadCascade::new(vec![Biquad::new(b, a); 2]);
+        let mut twice = Biquad::new(b, a);
+        let mut y = vec![0.0; x.len()];
+        c.process(&x, &mut y);
+        let t = twice.apply(&want);
+        assert!(y.iter().zip(&t).all(|(g, w)| (g - w).abs() < 1e-12));
+        c.reset();
+        assert_eq!(y, c.apply(&x));
+        assert_eq!(2, c.stages().len());
+    }
+
+    #[test]
+    fn averages_check() {
+        let x: Vec<f64> = (0..50).map(|i| ((i * 13 % 17) as f64) * 0.25).collect();
+        for w in [1, 3, 7] {
+            let mut s = Sma::new(w);
+            let mut m = Wma::new(w);
+            let sy = s.apply(&x);
+            let my = m.apply(&x);
+            for i in 0..x.len() {
+                let win = &x[(i + 1).saturating_sub(w)..=i];
+                let mean = win.iter().sum::<f64>() / win.len() as f64;
+                let k = win.len() as f64;
+                let wm = win
+                    .iter()
+                    .enumerate()
+                    .map(|(j, v)| (j + 1) as f64 * v)
+                    .sum::<f64>()
+                    / (k * (k + 1.0) / 2.0);
+                assert!((sy[i] - mean).abs() < 1e-12);
+                assert!((my[i] - wm).abs() < 1e-12);
+            }
+            s.reset();
+            assert_eq!(x[0], s.step(x[0]));
+            assert_eq!(w, m.window());
+        }
+
+        let mut e = Ema::with_span(3);
+        assert_eq!(0.5, e.alpha());
+        assert_eq!(None, e.value());
+        assert_eq!(vec![2.0, 3.0, 3.5], e.apply(&[2.0, 4.0, 4.0]));
+        e.reset();
+        assert_eq!(8.0, e.step(8.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_window_check() {
+        Sma::<f32>::new(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_a0_check() {
+        Biquad::new([1.0, 0.0, 0.0], [0.0, 1.0, 0.0]);
+    }
+}
@@ -0,0 +1,215 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::num::Float;
+use crate::window::{hamming, sinc, Symmetry};
+
+/// # DesignError
+/// Why a filter couldn't be designed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DesignError {
+    /// Asked for zero taps.
+    NoTaps,
+    /// A cutoff isn't strictly between 0 and half the
+    /// sample rate, or a band's edges are the wrong way round.
+    Cutoff,
+    /// A high-pass needs an odd number of taps: an even,
+    /// symmetric FIR always has a zero at half the sample rate.
+    EvenHighpass,
+}
+
+impl fmt::Display for DesignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DesignError::NoTaps => write!(f, "filter needs at least one tap"),
+            DesignError::Cutoff => {
+                write!(f, "cutoff has to be between 0 and half the sample rate")
+            }
+            DesignError::EvenHighpass => write!(f, "high-pass filter needs an odd number of taps"),
+        }
+    }
+}
+
+impl Error for DesignError {}
+
+/// Hamming-windowed ideal low-pass at `fc` cycles per sample,
+/// scaled to a gain of exactly one at DC. Worked out in f64.
+fn sinc_lowpass(n: usize, fc: f64) -> Vec<f64> {
+    let mid = (n - 1) as f64 / 2.0;
+    let mut h: Vec<f64> = hamming(n, Symmetry::Symmetric);
+    for (i, x) in h.iter_mut().enumerate() {
+        let t = i as f64 - mid;
+        *x *= 2.0 * fc * sinc(2.0 * fc * t);
+    }
+    let sum: f64 = h.iter().sum();
+    h.iter_mut().for_each(|x| *x /= sum);
+    h
+}
+
+/// Cutoff as cycles per sample, if it's in `(0, 1/2)`.
+fn normalised<T: Float>(cutoff: T, sample_rate: T) -> Result<f64, DesignError> {
+    let fc = cutoff.to_f64() / sample_rate.to_f64();
+    if fc > 0.0 && fc < 0.5 {
+        Ok(fc)
+    } else {
+        Err(DesignError::Cutoff)
+    }
+}
+
+/// # lowpass()
+/// Takes how many taps you want, the cutoff
+/// frequency and the sample rate (same units).
+///
+/// Returns FIR taps for a windowed-sinc
+/// low-pass (Hamming window) as a new shiny
+/// Vec\<T\>, with a gain of one at DC. More taps
+/// make a sharper transition. Ready for
+/// `Fir::new()`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::filter::{lowpass, Filter, Fir};
+///
+/// let taps = lowpass(31, 100.0f64, 1000.0).unwrap();
+/// assert!((taps.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+/// // A steady signal comes through once the taps fill up.
+/// let y = Fir::new(&taps).apply(&[2.0; 40]);
+/// assert!((y[39] - 2.0).abs() < 1e-12);
+/// ```
+pub fn lowpass<T: Float>(
+    num_taps: usize,
+    cutoff: T,
+    sample_rate: T,
+) -> Result<Vec<T>, DesignError> {
+    if num_taps == 0 {
+        return Err(DesignError::NoTaps);
+    }
+    let fc = normalised(cutoff, sample_rate)?;
+    Ok(sinc_lowpass(num_taps, fc)
+        .into_iter()
+        .map(T::from_f64)
+        .collect())
+}
+
+/// # highpass()
+/// Takes how many taps you want (an odd
+/// number), the cutoff frequency and the
+/// sample rate.
+///
+/// Returns FIR taps for a windowed-sinc
+/// high-pass: the `lowpass()` at the same
+/// cutoff, subtracted from a unit impulse.
+/// Gain is zero at DC.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::filter::{highpass, DesignError};
+///
+/// let taps = highpass(31, 100.0f64, 1000.0).unwrap();
+/// assert!(taps.iter().sum::<f64>().abs() < 1e-12);
+/// assert_eq!(Err(DesignError::EvenHighpass), highpass(30, 100.0, 1000.0));
+/// ```
+pub fn highpass<T: Float>(
+    num_taps: usize,
+    cutoff: T,
+    sample_rate: T,
+) -> Result<Vec<T>, DesignError> {
+    if num_taps == 0 {
+        return Err(DesignError::NoTaps);
+    }
+    if num_taps.is_multiple_of(2) {
+        return Err(DesignError::EvenHighpass);
+    }
+    let fc = normalised(cutoff, sample_rate)?;
+    let mut h = sinc_lowpass(num_taps, fc);
+    h.iter_mut().for_each(|x| *x = -*x);
+    h[num_taps / 2] += 1.0;
+    Ok(h.into_iter().map(T::from_f64).collect())
+}
+
+/// # bandpass()
+/// Takes how many taps you want, the low and
+/// high edges of the band and the sample rate.
+///
+/// Returns FIR taps for a windowed-sinc
+/// band-pass: the difference of two
+/// `lowpass()`es, so a gain of about one
+/// inside the band and zero at DC.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::filter::{bandpass, DesignError};
+///
+/// let taps = bandpass(41, 100.0f64, 200.0, 1000.0).unwrap();
+/// assert!(taps.iter().sum::<f64>().abs() < 1e-12);
+/// assert_eq!(Err(DesignError::Cutoff), bandpass(41, 200.0, 100.0, 1000.0));
+/// ```
+pub fn bandpass<T: Float>(
+    num_taps: usize,
+    low: T,
+    high: T,
+    sample_rate: T,
+) -> Result<Vec<T>, DesignError> {
+    if num_taps == 0 {
+        return Err(DesignError::NoTaps);
+    }
+    let (lo, hi) = (
+        normalised(low, sample_rate)?,
+        normalised(high, sample_rate)?,
+    );
+    if lo >= hi {
+        return Err(DesignError::Cutoff);
+    }
+    let h = sinc_lowpass(num_taps, hi);
+    let l = sinc_lowpass(num_taps, lo);
+    Ok(h.iter().zip(&l).map(|(h, l)| T::from_f64(h - l)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    /// `|H(f)|` for `f` in cycles per sample.
+    fn gain(h: &[f64], f: f64) -> f64 {
+        let (re, im) = h.iter().enumerate().fold((0.0, 0.0), |(re, im), (i, &x)| {
+            let a = -2.0 * PI * f * i as f64;
+            (re + x * a.cos(), im + x * a.sin())
+        });
+        re.hypot(im)
+    }
+
+    #[test]
+    fn response_check() {
+        let lp = lowpass(101, 0.1, 1.0).unwrap();
+        assert!((gain(&lp, 0.02) - 1.0).abs() < 0.01);
+        assert!(gain(&lp, 0.2) < 0.01);
+        assert!(lp.iter().zip(lp.iter().rev()).all(|(a, b)| a == b));
+
+        let hp = highpass(101, 0.1, 1.0).unwrap();
+        assert!(gain(&hp, 0.02) < 0.01);
+        assert!((gain(&hp, 0.3) - 1.0).abs() < 0.01);
+
+        let bp = bandpass(101, 0.1, 0.3, 1.0).unwrap();
+        assert!(gain(&bp, 0.03) < 0.01);
+        assert!((gain(&bp, 0.2) - 1.0).abs() < 0.01);
+        assert!(gain(&bp, 0.4) < 0.01);
+
+        let f: Vec<f32> = lowpass(8, 1.0, 4.0).unwrap();
+        assert!((f.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        assert_eq!(vec![1.0], lowpass(1, 0.25, 1.0).unwrap());
+    }
+
+    #[test]
+    fn error_check() {
+        assert_eq!(Err(DesignError::NoTaps), lowpass(0, 0.1, 1.0));
+        assert_eq!(Err(DesignError::Cutoff), lowpass(11, 0.5, 1.0));
+        assert_eq!(Err(DesignError::Cutoff), highpass(11, 0.0, 1.0));
+        assert_eq!(Err(DesignError::Cutoff), lowpass(11, f64::NAN, 1.0));
+        assert_eq!(Err(DesignError::Cutoff), bandpass(11, 0.2, 0.2, 1.0));
+        assert_eq!(
+            "high-pass filter needs an odd number of taps",
+            DesignError::EvenHighpass.to_string()
+        );
+    }
+}
@@ -0,0 +1,694 @@
+//! Interpolation and resampling.
+//!
+//! The interpolators take sample points `x` (strictly increasing) and
+//! values `y`, copy them, and then answer `eval(t)` for any `t`
+//! through `Interpolate`. Like the `stats` functions, only as many
+//! points as the shorter slice count.
+//!
+//! - `Linear` and `Nearest` hold the end values outside the data.
+//! - `CubicSpline` is twice continuously differentiable, with natural
+//!   or clamped ends, and keeps its end cubics going outside.
+//! - `Pchip` is a cubic that never overshoots: monotone data gives a
+//!   monotone curve. It extrapolates with its end cubics too.
+//! - `Lanczos` is windowed-sinc reconstruction of evenly spaced
+//!   samples, repeating the end samples outside.
+//!
+//! `resample()` changes the length of a whole signal through its
+//! spectrum; `resample_poly()` changes the rate of a stream by a
+//! ratio `up / down` with a polyphase FIR filter.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::complex::Complex;
+use crate::dot_slice;
+use crate::fft::{irfft, rfft};
+use crate::filter::lowpass;
+use crate::num::{Float, Zero};
+use crate::window::sinc;
+
+/// # InterpError
+/// Why an interpolator couldn't be built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InterpError {
+    /// Not enough points for the method.
+    TooFewPoints { needed: usize, got: usize },
+    /// `x` isn't strictly increasing (or has a NaN in it).
+    NotIncreasing,
+    /// `Lanczos` asked for zero lobes.
+    NoLobes,
+}
+
+impl fmt::Display for InterpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterpError::TooFewPoints { needed, got } => {
+                write!(f, "needs at least {needed} points, got {got}")
+            }
+            InterpError::NotIncreasing => write!(f, "x values aren't strictly increasing"),
+            InterpError::NoLobes => write!(f, "Lanczos needs at least one lobe"),
+        }
+    }
+}
+
+impl Error for InterpError {}
+
+/// # Interpolate
+/// Something that can be evaluated anywhere
+/// along the x axis.
+pub trait Interpolate {
+    type Item: Copy;
+
+    /// The interpolated value at `t`.
+    fn eval(&self, t: Self::Item) -> Self::Item;
+
+    /// `eval()` at each point, as a new shiny Vec.
+    fn eval_slice(&self, ts: &[Self::Item]) -> Vec<Self::Item> {
+        ts.iter().map(|&t| self.eval(t)).collect()
+    }
+}
+
+/// Copies of the first `min(x.len(), y.len())` points, checked.
+fn points<T: Float>(x: &[T], y: &[T], needed: usize) -> Result<(Vec<T>, Vec<T>), InterpError> {
+    let n = x.len().min(y.len());
+    if n < needed {
+        return Err(InterpError::TooFewPoints { needed, got: n });
+    }
+    // Written so a NaN fails it too.
+    if !x[..n].windows(2).all(|w| w[0] < w[1]) || x[0].is_nan() {
+        return Err(InterpError::NotIncreasing);
+    }
+    Ok((x[..n].to_vec(), y[..n].to_vec()))
+}
+
+/// The `i` with `x[i] <= t < x[i + 1]`, clamped to the first
+/// and last intervals. Needs two points.
+fn interval<T: Float>(x: &[T], t: T) -> usize {
+    x.partition_point(|&v| v <= t)
+        .saturating_sub(1)
+        .min(x.len() - 2)
+}
+
+/// # Linear
+/// Straight lines between the points, like
+/// NumPy's `interp()`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::interp::{Interpolate, Linear};
+///
+/// let f = Linear::new(&[0.0, 1.0, 3.0], &[0.0, 10.0, 30.0]).unwrap();
+/// assert_eq!(vec![0.0, 5.0, 20.0, 30.0], f.eval_slice(&[-1.0, 0.5, 2.0, 9.0]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Linear<T> {
+    x: Vec<T>,
+    y: Vec<T>,
+}
+
+impl<T: Float> Linear<T> {
+    /// Needs one point.
+    pub fn new(x: &[T], y: &[T]) -> Result<Self, InterpError> {
+        let (x, y) = points(x, y, 1)?;
+        Ok(Linear { x, y })
+    }
+}
+
+impl<T: Float> Interpolate for Linear<T> {
+    type Item = T;
+
+    fn eval(&self, t: T) -> T {
+        let (x, y) = (&self.x, &self.y);
+        let n = x.len();
+        if n == 1 || t <= x[0] {
+            return y[0];
+        }
+        if t >= x[n - 1] {
+            return y[n - 1];
+        }
+        let i = interval(x, t);
+        let s = (t - x[i]) / (x[i + 1] - x[i]);
+        s.mul_add(y[i + 1] - y[i], y[i])
+    }
+}
+
+/// # Nearest
+/// The value of the closest point. Halfway
+/// between two, the left one wins.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::interp::{Interpolate, Nearest};
+///
+/// let f = Nearest::new(&[0.0, 1.0, 3.0], &[5.0, 6.0, 7.0]).unwrap();
+/// assert_eq!(vec![5.0, 5.0, 6.0, 7.0], f.eval_slice(&[-4.0, 0.5, 1.9, 2.1]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nearest<T> {
+    x: Vec<T>,
+    y: Vec<T>,
+}
+
+impl<T: Float> Nearest<T> {
+    /// Needs one point.
+    pub fn new(x: &[T], y: &[T]) -> Result<Self, InterpError> {
+        let (x, y) = points(x, y, 1)?;
+        Ok(Nearest { x, y })
+    }
+}
+
+impl<T: Float> Interpolate for Nearest<T> {
+    type Item = T;
+
+    fn eval(&self, t: T) -> T {
+        if self.x.len() == 1 {
+            return self.y[0];
+        }
+        let i = interval(&self.x, t);
+        if t - self.x[i] <= self.x[i + 1] - t {
+            self.y[i]
+        } else {
+            self.y[i + 1]
+        }
+    }
+}
+
+/// # CubicSpline
+/// The piecewise cubic through the points
+/// with continuous first and second
+/// derivatives.
+///
+/// `natural()` has zero second derivative at
+/// both ends; `clamped()` takes the first
+/// derivative you want there.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::interp::{CubicSpline, Interpolate};
+///
+/// let f = CubicSpline::natural(&[0.0, 1.0, 2.0], &[0.0, 1.0, 0.0]).unwrap();
+/// assert!((f.eval(0.5f64) - 0.6875).abs() < 1e-12);
+/// assert_eq!(1.0, f.eval(1.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubicSpline<T> {
+    x: Vec<T>,
+    y: Vec<T>,
+    /// Second derivative at each point.
+    m: Vec<T>,
+}
+
+impl<T: Float> CubicSpline<T> {
+    /// Natural ends. Needs two points; with only
+    /// two it's a straight line.
+    pub fn natural(x: &[T], y: &[T]) -> Result<Self, InterpError> {
+        CubicSpline::build(x, y, None)
+    }
+
+    /// First derivative `d0` at the first point
+    /// and `dn` at the last. Needs two points.
+    pub fn clamped(x: &[T], y: &[T], d0: T, dn: T) -> Result<Self, InterpError> {
+        CubicSpline::build(x, y, Some((d0, dn)))
+    }
+
+    fn build(x: &[T], y: &[T], ends: Option<(T, T)>) -> Result<Self, InterpError> {
+        let (x, y) = points(x, y, 2)?;
+        let n = x.len();
+        let h: Vec<T> = x.windows(2).map(|w| w[1] - w[0]).collect();
+        let slope: Vec<T> = (0..n - 1).map(|i| (y[i + 1] - y[i]) / h[i]).collect();
+        let (two, six) = (T::from_f64(2.0), T::from_f64(6.0));
+
+        // Tridiagonal system for the second derivatives:
+        // sub[i]·m[i-1] + diag[i]·m[i] + sup[i]·m[i+1] = rhs[i].
+        let mut sub = vec![T::ZERO; n];
+        let mut diag = vec![T::ONE; n];
+        let mut sup = vec![T::ZERO; n];
+        let mut rhs = vec![T::ZERO; n];
+        for i in 1..n - 1 {
+            sub[i] = h[i - 1];
+            diag[i] = two * (h[i - 1] + h[i]);
+            sup[i] = h[i];
+            rhs[i] = six * (slope[i] - slope[i - 1]);
+        }
+        if let Some((d0, dn)) = ends {
+            diag[0] = two * h[0];
+            sup[0] = h[0];
+            rhs[0] = six * (slope[0] - d0);
+            sub[n - 1] = h[n - 2];
+            diag[n - 1] = two * h[n - 2];
+            rhs[n - 1] = six * (dn - slope[n - 2]);
+        }
+
+        // Thomas algorithm; diagonally dominant, so no pivoting.
+        for i in 1..n {
+            let w = sub[i] / diag[i - 1];
+            diag[i] = diag[i] - w * sup[i - 1];
+            rhs[i] = rhs[i] - w * rhs[i - 1];
+        }
+        let mut m = vec![T::ZERO; n];
+        m[n - 1] = rhs[n - 1] / diag[n - 1];
+        for i in (0..n - 1).rev() {
+            m[i] = (rhs[i] - sup[i] * m[i + 1]) / diag[i];
+        }
+        Ok(CubicSpline { x, y, m })
+    }
+}
+
+impl<T: Float> Interpolate for CubicSpline<T> {
+    type Item = T;
+
+    fn eval(&self, t: T) -> T {
+        let (x, y, m) = (&self.x, &self.y, &self.m);
+        let i = interval(x, t);
+        let h = x[i + 1] - x[i];
+        let (a, b) = (x[i + 1] - t, t - x[i]);
+        let six = T::from_f64(6.0);
+        (m[i] * a * a * a + m[i + 1] * b * b * b) / (six * h)
+            + (y[i] / h - m[i] * h / six) * a
+            + (y[i + 1] / h - m[i + 1] * h / six) * b
+    }
+}
+
+/// # Pchip
+/// Piecewise cubic Hermite interpolation with
+/// Fritsch–Carlson slopes, SciPy's
+/// `PchipInterpolator`. Flat wherever the data
+/// turns around, so it doesn't overshoot, and
+/// monotone data stays monotone.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::interp::{Interpolate, Pchip};
+///
+/// let f = Pchip::new(&[0.0, 1.0, 2.0, 3.0], &[0.0, 0.0, 1.0, 1.0]).unwrap();
+/// let y = f.eval_slice(&[0.5, 1.5, 2.5]);
+/// assert_eq!(vec![0.0, 0.5, 1.0], y);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pchip<T> {
+    x: Vec<T>,
+    y: Vec<T>,
+    /// First derivative at each point.
+    d: Vec<T>,
+}
+
+/// Does `a` have a different sign from `b`, zero counting as its own?
+fn sign_differs<T: Float>(a: T, b: T) -> bool {
+    let sign = |v: T| (v > T::ZERO) as i8 - (v < T::ZERO) as i8;
+    sign(a) != sign(b)
+}
+
+impl<T: Float> Pchip<T> {
+    /// Needs two points.
+    pub fn new(x: &[T], y: &[T]) -> Result<Self, InterpError> {
+        let (x, y) = points(x, y, 2)?;
+        let n = x.len();
+        let h: Vec<T> = x.windows(2).map(|w| w[1] - w[0]).collect();
+        let slope: Vec<T> = (0..n - 1).map(|i| (y[i + 1] - y[i]) / h[i]).collect();
+        if n == 2 {
+            return Ok(Pchip {
+                x,
+                y,
+                d: vec![slope[0]; 2],
+            });
+        }
+        let two = T::from_f64(2.0);
+        let mut d = vec![T::ZERO; n];
+        for k in 1..n - 1 {
+            let (s0, s1) = (slope[k - 1], slope[k]);
+            if s0 == T::ZERO || s1 == T::ZERO || sign_differs(s0, s1) {
+                continue;
+            }
+            // Weighted harmonic mean of the neighbouring slopes.
+            let w1 = two * h[k] + h[k - 1];
+            let w2 = h[k] + two * h[k - 1];
+            d[k] = (w1 + w2) / (w1 / s0 + w2 / s1);
+        }
+        d[0] = Pchip::end_slope(h[0], h[1], slope[0], slope[1]);
+        d[n - 1] = Pchip::end_slope(h[n - 2], h[n - 3], slope[n - 2], slope[n - 3]);
+        Ok(Pchip { x, y, d })
+    }
+
+    /// One-sided three-point slope at an end, reined in so it can't
+    /// overshoot; `h0`, `s0` are the end interval, `h1`, `s1` the next.
+    fn end_slope(h0: T, h1: T, s0: T, s1: T) -> T {
+        let d = ((T::from_f64(2.0) * h0 + h1) * s0 - h0 * s1) / (h0 + h1);
+        let three = T::from_f64(3.0);
+        if sign_differs(d, s0) {
+            T::ZERO
+        } else if sign_differs(s0, s1) && d.abs() > (three * s0).abs() {
+            three * s0
+        } else {
+            d
+        }
+    }
+}
+
+impl<T: Float> Interpolate for Pchip<T> {
+    type Item = T;
+
+    fn eval(&self, t: T) -> T {
+        let (x, y, d) = (&self.x, &self.y, &self.d);
+        let i = interval(x, t);
+        let h = x[i + 1] - x[i];
+        let slope = (y[i + 1] - y[i]) / h;
+        let two = T::from_f64(2.0);
+        let c = (T::from_f64(3.0) * slope - two * d[i] - d[i + 1]) / h;
+        let b = (d[i] - two * slope + d[i + 1]) / (h * h);
+        let s = t - x[i];
+        s.mul_add(s.mul_add(s.mul_add(b, c), d[i]), y[i])
+    }
+}
+
+/// # Lanczos
+/// Windowed-sinc reconstruction of evenly
+/// spaced samples `y`, the first at `start`
+/// and `step` apart, using `a` lobes each
+/// side (2 or 3 is usual). Goes through every
+/// sample, and the weights are normalised, so
+/// a constant stays constant.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::interp::{Interpolate, Lanczos};
+///
+/// let f = Lanczos::new(0.0, 0.5, &[1.0, 2.0, 4.0, 8.0], 2).unwrap();
+/// assert!((f.eval(1.0f64) - 4.0).abs() < 1e-12);
+/// let mid: f64 = f.eval(0.75);
+/// assert!(mid > 2.0 && mid < 4.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lanczos<T> {
+    start: T,
+    step: T,
+    y: Vec<T>,
+    a: usize,
+}
+
+impl<T: Float> Lanczos<T> {
+    /// Needs one sample, a positive `step` (or
+    /// it's `NotIncreasing`) and at least one lobe
+    /// (or it's `NoLobes`).
+    pub fn new(start: T, step: T, y: &[T], a: usize) -> Result<Self, InterpError> {
+        if y.is_empty() {
+            return Err(InterpError::TooFewPoints { needed: 1, got: 0 });
+        }
+        if step.is_nan() || step <= T::ZERO {
+            return Err(InterpError::NotIncreasing);
+        }
+        if a == 0 {
+            return Err(InterpError::NoLobes);
+        }
+        Ok(Lanczos {
+            start,
+            step,
+            y: y.to_vec(),
+            a,
+        })
+    }
+}
+
+impl<T: Float> Interpolate for Lanczos<T> {
+    type Item = T;
+
+    fn eval(&self, t: T) -> T {
+        // Past `a` samples beyond either end it's all the end sample
+        // anyway; clamping keeps `floor() as isize` (which saturates)
+        // from overflowing the loop bounds.
+        let (len, a) = (self.y.len() as f64, self.a as f64);
+        let u = ((t - self.start) / self.step).to_f64().clamp(-a, len + a);
+        let last = self.y.len() as isize - 1;
+        let a = self.a as isize;
+        let base = u.floor() as isize;
+        let (mut acc, mut total) = (T::ZERO, 0.0);
+        for k in base - a + 1..=base + a {
+            let r = u - k as f64;
+            let w = sinc(r) * sinc(r / a as f64);
+            acc = acc + T::from_f64(w) * self.y[k.clamp(0, last) as usize];
+            total += w;
+        }
+        acc / T::from_f64(total)
+    }
+}
+
+/// # resample()
+/// Takes a reference to a signal and the
+/// length you want.
+///
+/// Returns the signal stretched or squeezed to
+/// `new_len` samples over the same span, as a
+/// new shiny Vec\<T\>, by truncating or
+/// zero-padding its spectrum (SciPy's
+/// `resample()`). Treats the signal as one
+/// period of something periodic, so the ends
+/// influence each other; for streams, see
+/// `resample_poly()`. Empty input gives zeros.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::interp::resample;
+///
+/// let y = resample(&[1.0, 0.0, -1.0, 0.0], 8);
+/// let e = [1.0, 0.5f64.sqrt(), 0.0, -(0.5f64.sqrt()), -1.0, -(0.5f64.sqrt()), 0.0, 0.5f64.sqrt()];
+/// assert!(y.iter().zip(&e).all(|(a, b): (&f64, _)| (a - b).abs() < 1e-12));
+/// ```
+pub fn resample<T: Float>(a: &[T], new_len: usize) -> Vec<T> {
+    let n = a.len();
+    if n == 0 || new_len == 0 {
+        return vec![T::ZERO; new_len];
+    }
+    let x = rfft(a);
+    let k = n.min(new_len);
+    let mut y = vec![Complex::ZERO; new_len / 2 + 1];
+    y[..k / 2 + 1].copy_from_slice(&x[..k / 2 + 1]);
+    // The last bin kept is a Nyquist bin on one side and not the
+    // other, and `irfft()` only counts a Nyquist bin once.
+    if k.is_multiple_of(2) && n != new_len {
+        let f = T::from_f64(if new_len < n { 2.0 } else { 0.5 });
+        y[k / 2] = y[k / 2] * f;
+    }
+    let scale = T::from_f64(new_len as f64 / n as f64);
+    irfft(&y, new_len).into_iter().map(|v| v * scale).collect()
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// # resample_poly()
+/// Takes a reference to a signal and the
+/// ratio `up / down` to change its rate by.
+///
+/// Returns `ceil(len · up / down)` samples as
+/// a new shiny Vec\<T\>: upsampled by `up`,
+/// low-pass filtered, downsampled by `down`,
+/// done polyphase so it never builds the
+/// upsampled signal. The filter is a
+/// `lowpass()` with `20·max(up, down) + 1`
+/// taps, centred so the output lines up with
+/// the input; samples near the ends see zeros
+/// past them and droop, like SciPy's
+/// `resample_poly()`.
+///
+/// Panics if `up` or `down` is 0.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::interp::resample_poly;
+///
+/// let x = vec![1.0f64; 40];
+/// let y = resample_poly(&x, 3, 2);
+/// assert_eq!(60, y.len());
+/// assert!((y[30] - 1.0).abs() < 1e-2);
+/// ```
+pub fn resample_poly<T: Float>(a: &[T], up: usize, down: usize) -> Vec<T> {
+    assert!(up > 0 && down > 0, "resampling ratio {up}/{down}");
+    let g = gcd(up, down);
+    let (up, down) = (up / g, down / g);
+    if up == down {
+        return a.to_vec();
+    }
+    let n = a.len();
+    let out_len = (n * up).div_ceil(down);
+    let half = 10 * up.max(down);
+    let cutoff = T::from_f64(0.5 / up.max(down) as f64);
+    let h: Vec<T> = lowpass(2 * half + 1, cutoff, T::ONE)
+        .expect("cutoff below half the sample rate")
+        .into_iter()
+        .map(|v| v * T::from_f64(up as f64))
+        .collect();
+
+    // Phase p uses taps p, p + up, ...; reversed so each output
+    // is one dot_slice() against a run of the input.
+    let phases: Vec<Vec<T>> = (0..up)
+        .map(|p| h.iter().skip(p).step_by(up).rev().copied().collect())
+        .collect();
+    (0..out_len)
+        .map(|m| {
+            // Output m sits at position m·down + half of the
+            // upsampled signal as it comes out of the filter.
+            let j = m * down + half;
+            let taps = &phases[j % up];
+            let (q, len) = (j / up, taps.len());
+            let lo = (q + 1).saturating_sub(len);
+            let hi = q.min(n.saturating_sub(1));
+            if n == 0 || lo > hi {
+                return T::ZERO;
+            }
+            dot_slice(&a[lo..=hi], &taps[len - 1 + lo - q..])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::close;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn error_check() {
+        assert_eq!(
+            Err(InterpError::TooFewPoints { needed: 2, got: 1 }),
+            CubicSpline::natural(&[1.0], &[1.0, 2.0])
+        );
+        assert_eq!(
+            Err(InterpError::NotIncreasing),
+            Pchip::new(&[0.0, 2.0, 1.0], &[1.0, 2.0, 3.0])
+        );
+        assert_eq!(
+            Err(InterpError::NotIncreasing),
+            Linear::new(&[f64::NAN, 1.0], &[1.0, 2.0])
+        );
+        assert_eq!(
+            Err(InterpError::NotIncreasing),
+            Linear::new(&[1.0, 1.0], &[1.0, 2.0])
+        );
+        assert_eq!(
+            Err(InterpError::NotIncreasing),
+            Lanczos::new(0.0, 0.0, &[1.0], 2)
+        );
+        assert_eq!(Err(InterpError::NoLobes), Lanczos::new(0.0, 1.0, &[1.0], 0));
+        assert!(Lanczos::<f64>::new(0.0, 1.0, &[], 2).is_err());
+        assert_eq!(
+            "needs at least 2 points, got 1",
+            InterpError::TooFewPoints { needed: 2, got: 1 }.to_string()
+        );
+        assert_eq!(3.0, Linear::new(&[1.0], &[3.0]).unwrap().eval(-5.0));
+    }
+
+    #[test]
+    fn spline_check() {
+        // A clamped spline with the right end slopes is the cubic itself.
+        let p = |t: f64| 1.0 - 2.0 * t + 0.5 * t * t + 0.25 * t * t * t;
+        let dp = |t: f64| -2.0 + t + 0.75 * t * t;
+        let x = [-2.0, -0.5, 0.0, 1.5, 2.0, 4.0];
+        let y: Vec<f64> = x.iter().map(|&t| p(t)).collect();
+        let s = CubicSpline::clamped(&x, &y, dp(-2.0), dp(4.0)).unwrap();
+        let ts: Vec<f64> = (0..25).map(|i| -2.0 + i as f64 * 0.25).collect();
+        let want: Vec<f64> = ts.iter().map(|&t| p(t)).collect();
+        assert!(close(&s.eval_slice(&ts), &want, 1e-10));
+
+        // Natural: through the points, no curvature at the ends.
+        let n = CubicSpline::natural(&x, &y).unwrap();
+        assert!(close(&n.eval_slice(&x), &y, 1e-12));
+        let e = 1e-3;
+        let (a, b, c) = (n.eval(4.0 - e), n.eval(4.0), n.eval(4.0 + e));
+        assert!(((a - 2.0 * b + c) / (e * e)).abs() < 1e-5);
+        let two = CubicSpline::natural(&[0.0, 2.0], &[1.0, 5.0]).unwrap();
+        assert_eq!(3.0, two.eval(1.0));
+    }
+
+    #[test]
+    fn pchip_check() {
+        let x = [0.0, 1.0, 2.5, 3.0, 4.0, 6.0];
+        let y = [0.0, 0.1, 0.1, 2.0, 2.1, 5.0];
+        let f = Pchip::new(&x, &y).unwrap();
+        assert!(close(&f.eval_slice(&x), &y, 1e-12));
+        let ts: Vec<f64> = (0..=120).map(|i| i as f64 * 0.05).collect();
+        let v = f.eval_slice(&ts);
+        assert!(v.windows(2).all(|w| w[1] >= w[0] - 1e-12));
+        // Flat stretch stays flat.
+        assert!((f.eval(1.75) - 0.1).abs() < 1e-12);
+
+        // Straight-line data stays a straight line.
+        let g = Pchip::new(&[0.0, 1.0, 3.0, 4.0], &[1.0, 3.0, 7.0, 9.0]).unwrap();
+        assert!(close(
+            &g.eval_slice(&[0.5, 2.0, 3.9]),
+            &[2.0, 5.0, 8.8],
+            1e-12
+        ));
+    }
+
+    #[test]
+    fn linear_nearest_lanczos_check() {
+        let x = [0.0f32, 2.0, 3.0];
+        let y = [1.0f32, 5.0, -1.0];
+        let l = Linear::new(&x, &y).unwrap();
+        assert_eq!(
+            vec![1.0, 3.0, 2.0, -1.0],
+            l.eval_slice(&[-1.0, 1.0, 2.5, 7.0])
+        );
+        let n = Nearest::new(&x, &y).unwrap();
+        assert_eq!(
+            vec![1.0, 1.0, 5.0, 5.0, -1.0],
+            n.eval_slice(&[-1.0, 1.0, 1.5, 2.5, 2.6])
+        );
+
+        let samples: Vec<f64> = (0..12).map(|i| (i as f64 * 0.4).sin()).collect();
+        let f = Lanczos::new(1.0, 0.5, &samples, 3).unwrap();
+        let at: Vec<f64> = (0..12).map(|i| 1.0 + i as f64 * 0.5).collect();
+        assert!(close(&f.eval_slice(&at), &samples, 1e-12));
+        // Smooth data in the middle comes out close to the curve.
+        let t: f64 = 1.0 + 5.25 * 0.5;
+        assert!((f.eval(t) - (5.25f64 * 0.4).sin()).abs() < 1e-2);
+        let flat = Lanczos::new(0.0, 1.0, &[2.5; 6], 2).unwrap();
+        assert!((flat.eval(-0.3) - 2.5).abs() < 1e-12);
+        assert!((flat.eval(2.7) - 2.5).abs() < 1e-12);
+
+        // Far away (or infinitely) it's the end samples.
+        assert!((f.eval(1e300) - samples[11]).abs() < 1e-12);
+        assert!((f.eval(f64::INFINITY) - samples[11]).abs() < 1e-12);
+        assert!((f.eval(-1e300) - samples[0]).abs() < 1e-12);
+        assert!((f.eval(f64::NEG_INFINITY) - samples[0]).abs() < 1e-12);
+        assert!(f.eval(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn resample_check() {
+        let tone = |n: usize| -> Vec<f64> {
+            (0..n)
+                .map(|i| (2.0 * PI * 3.0 * i as f64 / n as f64).cos())
+                .collect()
+        };
+        assert!(close(&resample(&tone(16), 40), &tone(40), 1e-12));
+        assert!(close(&resample(&tone(40), 16), &tone(16), 1e-12));
+        assert!(close(&resample(&tone(15), 15), &tone(15), 1e-12));
+        assert!(close(&resample(&tone(9), 13), &tone(13), 1e-12));
+        assert_eq!(vec![0.0; 3], resample::<f64>(&[], 3));
+        assert!(resample(&[1.0], 0).is_empty());
+    }
+
+    #[test]
+    fn resample_poly_check() {
+        let f = 0.02;
+        let x: Vec<f64> = (0..200).map(|i| (2.0 * PI * f * i as f64).sin()).collect();
+        let y = resample_poly(&x, 5, 3);
+        assert_eq!(334, y.len());
+        // Away from the ends, the new samples sit on the same sine.
+        for (m, &v) in y.iter().enumerate().skip(60).take(200) {
+            let t = m as f64 * 3.0 / 5.0;
+            assert!((v - (2.0 * PI * f * t).sin()).abs() < 1e-2);
+        }
+        let down = resample_poly(&x, 2, 4);
+        assert_eq!(100, down.len());
+        assert!((down[50] - (2.0 * PI * f * 100.0).sin()).abs() < 1e-2);
+        assert_eq!(x, resample_poly(&x, 3, 3));
+        assert!(resample_poly::<f32>(&[], 2, 1).is_empty());
+        assert_eq!(4, resample_poly(&[1.0f32, 2.0], 2, 1).len());
+    }
+}
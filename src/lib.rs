@@ -1,66 +1,528 @@
-use std::iter::Sum;
-use std::ops::Mul;
+use std::ops::{BitAnd, BitOr, BitXor, Rem};
 
-/// # mul_slice()
-/// Takes a reference to a couple slices of
-/// type \<T\>.
-///
-/// Returns a new shiny Vec\<T\> of both
-/// slices multiplied together.
-///
-/// Slices don't have to match in size but
-/// will only return products upto the size of
-/// smallest slice.
+pub mod blas1;
+pub mod broadcast;
+pub mod complex;
+pub mod convolve;
+pub mod distance;
+pub mod fft;
+pub mod filter;
+pub mod interp;
+pub mod linalg;
+pub mod matrix;
+pub mod norm;
+pub mod num;
+pub mod overflow;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+mod policy;
+pub mod poly;
+pub mod reduce;
+mod simd;
+pub mod stats;
+pub mod strided;
+mod summation;
+#[cfg(test)]
+mod test_util;
+pub mod window;
+
+pub use broadcast::{Broadcast, Scalar};
+pub use num::{Field, Float, Integer, Num, One, Zero};
+pub use policy::{try_dot_slice, try_mul_slice, LengthMismatch, LengthPolicy};
+pub use strided::{SliceInput, StridedSlice};
+pub use summation::{
+    dot_slice_dot2, dot_slice_f64acc, dot_slice_kahan, dot_slice_pairwise, dot_slice_with,
+    Summation,
+};
+
+/// Stamps out an element-wise binary op over a couple of slices.
 ///
-/// ## Example:
-/// ```rust
-/// fn mul_slice_check() {
-///		let t: Vec<_> = (0..10).step_by(2).collect();
-///		let u: Vec<_> = (0..20).step_by(4).collect();
-///		let q: Vec<_> = mul_slice(&t, &u);
-///		let e: Vec<_> = vec![0, 8, 32, 72, 128];
-///		assert_eq!(e, q)
-/// }
-/// ```
-pub fn mul_slice<T: Mul + Mul<Output = T> + Copy + Clone>(a: &[T], b: &[T]) -> Vec<T> {
-    let len = if a.len() <= b.len() { a.len() } else { b.len() };
-    let mut v: Vec<T> = Vec::new();
+/// Every op gets the same three flavours: one that collects into a
+/// fresh Vec\<T\>, one that writes into a caller's buffer, and one
+/// that overwrites the left slice in place. All of them stop at the
+/// shortest input. Inputs are anything `Broadcast`, so slices, strided
+/// slices and scalars; when both are contiguous they go down the slice
+/// path (and the fast kernel, if there is one), otherwise it's an
+/// indexed loop. Keeps the whole family consistent.
+macro_rules! elementwise_op {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $into:ident, $assign:ident,
+        [$($bound:tt)+],
+        |$x:ident, $y:ident| $body:expr
+        $(, fast: $fast:path)?
+    ) => {
+        $(#[$meta])*
+        pub fn $name<T>(
+            a: &(impl Broadcast<Item = T> + ?Sized),
+            b: &(impl Broadcast<Item = T> + ?Sized),
+        ) -> Vec<T>
+        where
+            T: $($bound)+,
+        {
+            if let (Some(a), Some(b)) = (a.contiguous(), b.contiguous()) {
+                $(
+                    if let Some(kernel) = $fast() {
+                        let len = a.len().min(b.len());
+                        let mut v = a[..len].to_vec();
+                        kernel(&a[..len], &b[..len], &mut v);
+                        return v;
+                    }
+                )?
+                return a
+                    .iter()
+                    .zip(b.iter())
+                    .map(|(&$x, &$y)| $body)
+                    .collect();
+            }
+            (0..broadcast::extent(a, b))
+                .map(|i| {
+                    let ($x, $y) = (a.item(i), b.item(i));
+                    $body
+                })
+                .collect()
+        }
 
-    for i in 0..len {
-        v.push(a[i] * b[i]);
-    }
-    v
+        #[doc = concat!("# ", stringify!($into), "()")]
+        #[doc = concat!("Same as `", stringify!($name), "()` but writes into `out`")]
+        /// instead of allocating.
+        ///
+        /// Only goes up to the size of the smallest of
+        /// `a`, `b` and `out`; anything past that in `out`
+        /// is left alone.
+        ///
+        /// Returns how many elements were written.
+        pub fn $into<T>(
+            a: &(impl Broadcast<Item = T> + ?Sized),
+            b: &(impl Broadcast<Item = T> + ?Sized),
+            out: &mut [T],
+        ) -> usize
+        where
+            T: $($bound)+,
+        {
+            if let (Some(a), Some(b)) = (a.contiguous(), b.contiguous()) {
+                $(
+                    if let Some(kernel) = $fast() {
+                        let len = a.len().min(b.len()).min(out.len());
+                        kernel(&a[..len], &b[..len], &mut out[..len]);
+                        return len;
+                    }
+                )?
+                let mut n = 0;
+                for ((o, &$x), &$y) in out.iter_mut().zip(a.iter()).zip(b.iter()) {
+                    *o = $body;
+                    n += 1;
+                }
+                return n;
+            }
+            let n = broadcast::extent(a, b).min(out.len());
+            for (i, o) in out[..n].iter_mut().enumerate() {
+                let ($x, $y) = (a.item(i), b.item(i));
+                *o = $body;
+            }
+            n
+        }
+
+        #[doc = concat!("# ", stringify!($assign), "()")]
+        #[doc = concat!("Same as `", stringify!($name), "()` but overwrites `a`")]
+        /// with the result, no allocation.
+        ///
+        /// Only goes up to the size of the smallest slice;
+        /// anything past that in `a` is left alone.
+        ///
+        /// Returns how many elements were written.
+        pub fn $assign<T>(a: &mut [T], b: &(impl Broadcast<Item = T> + ?Sized)) -> usize
+        where
+            T: $($bound)+,
+        {
+            let n = b.extent().map_or(a.len(), |len| len.min(a.len()));
+            for (i, o) in a[..n].iter_mut().enumerate() {
+                let ($x, $y) = (*o, b.item(i));
+                *o = $body;
+            }
+            n
+        }
+    };
 }
 
+elementwise_op!(
+    /// # mul_slice()
+    /// Takes a reference to a couple slices of
+    /// type \<T\>.
+    ///
+    /// Returns a new shiny Vec\<T\> of both
+    /// slices multiplied together.
+    ///
+    /// Slices don't have to match in size but
+    /// will only return products upto the size of
+    /// smallest slice.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::mul_slice;
+    ///
+    /// let t: Vec<_> = (0..10).step_by(2).collect();
+    /// let u: Vec<_> = (0..20).step_by(4).collect();
+    /// let q: Vec<_> = mul_slice(&t, &u);
+    /// let e: Vec<_> = vec![0, 8, 32, 72, 128];
+    /// assert_eq!(e, q)
+    /// ```
+    mul_slice,
+    mul_slice_into,
+    mul_assign_slice,
+    [Num],
+    |x, y| x * y,
+    fast: T::mul_kernel
+);
+
+elementwise_op!(
+    /// # add_slice()
+    /// Takes a reference to a couple slices of
+    /// type \<T\>.
+    ///
+    /// Returns a new shiny Vec\<T\> of both
+    /// slices added together.
+    ///
+    /// Same deal as `mul_slice()`, only goes
+    /// up to the size of the smallest slice.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::add_slice;
+    ///
+    /// let q = add_slice(&[1, 2, 3], &[10, 20, 30, 40]);
+    /// assert_eq!(vec![11, 22, 33], q)
+    /// ```
+    add_slice,
+    add_slice_into,
+    add_assign_slice,
+    [Num],
+    |x, y| x + y
+);
+
+elementwise_op!(
+    /// # sub_slice()
+    /// Takes a reference to a couple slices of
+    /// type \<T\>.
+    ///
+    /// Returns a new shiny Vec\<T\> of `b`
+    /// subtracted from `a`.
+    ///
+    /// Only goes up to the size of the smallest slice.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::sub_slice;
+    ///
+    /// let q = sub_slice(&[10, 20, 30], &[1, 2, 3]);
+    /// assert_eq!(vec![9, 18, 27], q)
+    /// ```
+    sub_slice,
+    sub_slice_into,
+    sub_assign_slice,
+    [Num],
+    |x, y| x - y
+);
+
+elementwise_op!(
+    /// # div_slice()
+    /// Takes a reference to a couple slices of
+    /// type \<T\>.
+    ///
+    /// Returns a new shiny Vec\<T\> of `a`
+    /// divided by `b`.
+    ///
+    /// Only goes up to the size of the smallest slice.
+    /// Integer division by zero panics, same as `/`.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::div_slice;
+    ///
+    /// let q = div_slice(&[10, 20, 30], &[2, 4, 7]);
+    /// assert_eq!(vec![5, 5, 4], q)
+    /// ```
+    div_slice,
+    div_slice_into,
+    div_assign_slice,
+    [Num],
+    |x, y| x / y
+);
+
+elementwise_op!(
+    /// # rem_slice()
+    /// Takes a reference to a couple slices of
+    /// type \<T\>.
+    ///
+    /// Returns a new shiny Vec\<T\> of the
+    /// remainders of `a` divided by `b`.
+    ///
+    /// Only goes up to the size of the smallest slice.
+    /// Integer remainder by zero panics, same as `%`.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::rem_slice;
+    ///
+    /// let q = rem_slice(&[10, 20, 30], &[3, 6, 7]);
+    /// assert_eq!(vec![1, 2, 2], q)
+    /// ```
+    rem_slice,
+    rem_slice_into,
+    rem_assign_slice,
+    [Num + Rem<Output = T>],
+    |x, y| x % y
+);
+
+elementwise_op!(
+    /// # min_slice()
+    /// Takes a reference to a couple slices of
+    /// type \<T\>.
+    ///
+    /// Returns a new shiny Vec\<T\> holding the
+    /// smaller of each pair.
+    ///
+    /// Only goes up to the size of the smallest slice.
+    /// If the pair can't be compared (NaN), you get `b`.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::min_slice;
+    ///
+    /// let q = min_slice(&[1, 5, 3], &[4, 2, 6]);
+    /// assert_eq!(vec![1, 2, 3], q)
+    /// ```
+    min_slice,
+    min_slice_into,
+    min_assign_slice,
+    [PartialOrd + Copy + Clone],
+    |x, y| if x < y { x } else { y }
+);
+
+elementwise_op!(
+    /// # max_slice()
+    /// Takes a reference to a couple slices of
+    /// type \<T\>.
+    ///
+    /// Returns a new shiny Vec\<T\> holding the
+    /// bigger of each pair.
+    ///
+    /// Only goes up to the size of the smallest slice.
+    /// If the pair can't be compared (NaN), you get `b`.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::max_slice;
+    ///
+    /// let q = max_slice(&[1, 5, 3], &[4, 2, 6]);
+    /// assert_eq!(vec![4, 5, 6], q)
+    /// ```
+    max_slice,
+    max_slice_into,
+    max_assign_slice,
+    [PartialOrd + Copy + Clone],
+    |x, y| if x > y { x } else { y }
+);
+
+elementwise_op!(
+    /// # and_slice()
+    /// Takes a reference to a couple slices of
+    /// integers (or bools).
+    ///
+    /// Returns a new shiny Vec\<T\> of both
+    /// slices bitwise and'ed together.
+    ///
+    /// Only goes up to the size of the smallest slice.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::and_slice;
+    ///
+    /// let q = and_slice(&[0b1100u8, 0b1010], &[0b1010, 0b0110]);
+    /// assert_eq!(vec![0b1000, 0b0010], q)
+    /// ```
+    and_slice,
+    and_slice_into,
+    and_assign_slice,
+    [BitAnd + BitAnd<Output = T> + Copy + Clone],
+    |x, y| x & y
+);
+
+elementwise_op!(
+    /// # or_slice()
+    /// Takes a reference to a couple slices of
+    /// integers (or bools).
+    ///
+    /// Returns a new shiny Vec\<T\> of both
+    /// slices bitwise or'ed together.
+    ///
+    /// Only goes up to the size of the smallest slice.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::or_slice;
+    ///
+    /// let q = or_slice(&[0b1100u8, 0b1010], &[0b1010, 0b0110]);
+    /// assert_eq!(vec![0b1110, 0b1110], q)
+    /// ```
+    or_slice,
+    or_slice_into,
+    or_assign_slice,
+    [BitOr + BitOr<Output = T> + Copy + Clone],
+    |x, y| x | y
+);
+
+elementwise_op!(
+    /// # xor_slice()
+    /// Takes a reference to a couple slices of
+    /// integers (or bools).
+    ///
+    /// Returns a new shiny Vec\<T\> of both
+    /// slices bitwise xor'ed together.
+    ///
+    /// Only goes up to the size of the smallest slice.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::xor_slice;
+    ///
+    /// let q = xor_slice(&[0b1100u8, 0b1010], &[0b1010, 0b0110]);
+    /// assert_eq!(vec![0b0110, 0b1100], q)
+    /// ```
+    xor_slice,
+    xor_slice_into,
+    xor_assign_slice,
+    [BitXor + BitXor<Output = T> + Copy + Clone],
+    |x, y| x ^ y
+);
+
+/// Stamps out a slice-by-scalar shortcut over one of the ops above.
+macro_rules! scalar_op {
+    ($(#[$meta:meta])* $name:ident, $op:ident) => {
+        $(#[$meta])*
+        pub fn $name<T: Num>(a: &(impl SliceInput<Item = T> + ?Sized), k: T) -> Vec<T> {
+            $op(a, &Scalar(k))
+        }
+    };
+}
+
+scalar_op!(
+    /// # mul_scalar()
+    /// Takes a reference to a slice and a scalar.
+    ///
+    /// Returns a new shiny Vec\<T\> with every
+    /// element multiplied by the scalar. Same as
+    /// `mul_slice(a, &Scalar(k))`.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::mul_scalar;
+    ///
+    /// assert_eq!(vec![2.0f32, 4.0, 6.0], mul_scalar(&[1.0, 2.0, 3.0], 2.0));
+    /// ```
+    mul_scalar,
+    mul_slice
+);
+
+scalar_op!(
+    /// # add_scalar()
+    /// Takes a reference to a slice and a scalar.
+    ///
+    /// Returns a new shiny Vec\<T\> with the
+    /// scalar added to every element.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::add_scalar;
+    ///
+    /// assert_eq!(vec![11u8, 12, 13], add_scalar(&[1, 2, 3], 10));
+    /// ```
+    add_scalar,
+    add_slice
+);
+
+scalar_op!(
+    /// # sub_scalar()
+    /// Takes a reference to a slice and a scalar.
+    ///
+    /// Returns a new shiny Vec\<T\> with the
+    /// scalar taken off every element. For the
+    /// other way round, `sub_slice(&Scalar(k), a)`.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::{sub_scalar, sub_slice};
+    ///
+    /// assert_eq!(vec![0, 1, 2], sub_scalar(&[1, 2, 3], 1));
+    /// assert_eq!(vec![9, 8, 7], sub_slice(&10, &[1, 2, 3]));
+    /// ```
+    sub_scalar,
+    sub_slice
+);
+
+scalar_op!(
+    /// # div_scalar()
+    /// Takes a reference to a slice and a scalar.
+    ///
+    /// Returns a new shiny Vec\<T\> with every
+    /// element divided by the scalar.
+    ///
+    /// ## Example:
+    /// ```rust
+    /// use slicenator::div_scalar;
+    ///
+    /// assert_eq!(vec![0.5, 1.0, 1.5], div_scalar(&[1.0, 2.0, 3.0], 2.0));
+    /// ```
+    div_scalar,
+    div_slice
+);
+
 /// # dot_slice()
 /// Takes a reference to a couple of slices.
 ///
 /// Returns dot product of type \<T\>.
 ///
+/// Either side can also be a `StridedSlice`
+/// (or anything `SliceInput`), say a matrix
+/// column or every other sample.
+///
 /// Slice sizes don't have to match,
 /// but you will only calculate for the shortest slice.
 ///
+/// f32 and f64 get a SIMD fast path (AVX-512/AVX/
+/// SSE2 or NEON, whatever your CPU has). Floats may
+/// come out a hair different from a plain loop
+/// since the lanes get summed in chunks.
+///
+/// i32 and u32 get one too, but only in builds
+/// without `debug_assertions`, since the lanes
+/// wrap on overflow. A profile with
+/// `overflow-checks = true` and no debug
+/// assertions wraps there instead of panicking.
+///
 /// ## Example:
 /// ```rust
-/// fn dot_slice_check() {
-///     let t: Vec<_> = (0..10).step_by(2).collect();
-///     let u: Vec<_> = (0..20).step_by(4).collect();
-///     let q: i32 = dot_slice(&t, &u) - 198;
-///     let e: i32 = 42;
-///     assert_eq!(e, q)
-/// }
+/// use slicenator::dot_slice;
+///
+/// let t: Vec<_> = (0..10).step_by(2).collect();
+/// let u: Vec<_> = (0..20).step_by(4).collect();
+/// let q: i32 = dot_slice(&t, &u) - 198;
+/// let e: i32 = 42;
+/// assert_eq!(e, q)
 /// ```
-pub fn dot_slice<T: Mul + Mul<Output = T> + Copy + Clone + for<'a> Sum<&'a T>>(
-    a: &[T],
-    b: &[T],
+pub fn dot_slice<T: Num>(
+    a: &(impl SliceInput<Item = T> + ?Sized),
+    b: &(impl SliceInput<Item = T> + ?Sized),
 ) -> T {
-    let len = if a.len() <= b.len() { a.len() } else { b.len() };
-    let mut v: Vec<T> = Vec::new();
-
-    for i in 0..len {
-        v.push(a[i] * b[i]);
+    if let (Some(a), Some(b)) = (a.as_contiguous(), b.as_contiguous()) {
+        let len = if a.len() <= b.len() { a.len() } else { b.len() };
+        let (a, b) = (&a[..len], &b[..len]);
+        if let Some(kernel) = T::dot_kernel() {
+            return kernel(a, b);
+        }
+        return a.iter().zip(b).fold(T::ZERO, |acc, (&x, &y)| acc + x * y);
     }
-    v.iter().sum()
+    (0..a.len().min(b.len())).fold(T::ZERO, |acc, i| acc + a.at(i) * b.at(i))
 }
 
 #[cfg(test)]
@@ -112,4 +574,145 @@ mod tests {
         let e: i32 = 42;
         assert_eq!(e, q)
     }
+
+    #[test]
+    fn add_sub_slice_check() {
+        let t: Vec<_> = (0..10).step_by(2).collect();
+        let u: Vec<_> = (0..10).step_by(4).collect();
+        assert_eq!(vec![0, 6, 12], add_slice(&t, &u));
+        assert_eq!(vec![0, -2, -4], sub_slice(&t, &u));
+    }
+
+    #[test]
+    fn div_rem_slice_check() {
+        let t = [7.5, 9.0, 1.0];
+        let u = [2.5, 3.0];
+        assert_eq!(vec![3.0, 3.0], div_slice(&t, &u));
+        assert_eq!(vec![1, 0], rem_slice(&[7, 9, 1], &[3, 3]));
+    }
+
+    #[test]
+    fn min_max_slice_check() {
+        let t = [1.0, f64::NAN, 3.0];
+        let u = [2.0, 2.0, -3.0];
+        assert_eq!(vec![1.0, 2.0, -3.0], min_slice(&t, &u));
+        assert_eq!(vec![2.0, 2.0, 3.0], max_slice(&t, &u));
+    }
+
+    #[test]
+    fn bitwise_slice_check() {
+        let t = [0xf0u8, 0x0f, 0xff];
+        let u = [0xff, 0xff];
+        assert_eq!(vec![0xf0, 0x0f], and_slice(&t, &u));
+        assert_eq!(vec![0xff, 0xff], or_slice(&t, &u));
+        assert_eq!(vec![0x0f, 0xf0], xor_slice(&t, &u));
+        assert_eq!(vec![false, true], xor_slice(&[true, false], &[true, true]));
+    }
+
+    #[test]
+    fn mul_slice_into_check() {
+        let t: Vec<_> = (0..10).step_by(2).collect();
+        let u: Vec<_> = (0..20).step_by(4).collect();
+        let mut out = [-1; 7];
+        let n = mul_slice_into(&t, &u, &mut out);
+        assert_eq!(5, n);
+        assert_eq!([0, 8, 32, 72, 128, -1, -1], out);
+
+        let mut short = [0; 2];
+        assert_eq!(2, mul_slice_into(&t, &u, &mut short));
+        assert_eq!([0, 8], short);
+    }
+
+    #[test]
+    fn mul_assign_slice_check() {
+        let mut t: Vec<_> = (0..10).step_by(2).collect();
+        let u: Vec<_> = (0..10).step_by(4).collect();
+        let n = mul_assign_slice(&mut t, &u);
+        assert_eq!(3, n);
+        assert_eq!(vec![0, 8, 32, 6, 8], t);
+    }
+
+    #[test]
+    fn assign_slice_family_check() {
+        let mut t = [10, 20, 30];
+        sub_assign_slice(&mut t, &[1, 2, 3]);
+        assert_eq!([9, 18, 27], t);
+        div_assign_slice(&mut t, &[3, 3, 3]);
+        assert_eq!([3, 6, 9], t);
+        max_assign_slice(&mut t, &[5, 5, 5]);
+        assert_eq!([5, 6, 9], t);
+
+        let mut out = [0u8; 3];
+        assert_eq!(
+            2,
+            xor_slice_into(&[0xff, 0x0f, 0xf0], &[0x0f, 0x0f], &mut out)
+        );
+        assert_eq!([0xf0, 0x00, 0x00], out);
+    }
+
+    #[test]
+    fn num_wrapper_check() {
+        use std::num::Wrapping;
+        let t = [Wrapping(200u8), Wrapping(100)];
+        let u = [Wrapping(2u8), Wrapping(1)];
+        assert_eq!(Wrapping(244), dot_slice(&t, &u));
+        assert_eq!(vec![Wrapping(144), Wrapping(100)], mul_slice(&t, &u));
+    }
+
+    #[test]
+    fn strided_input_check() {
+        let data: Vec<f64> = (0..12).map(|k| k as f64).collect();
+        let even = StridedSlice::new(&data, 6, 2).unwrap();
+        let back = StridedSlice::new(&data, 12, -1).unwrap();
+        let e: f64 = (0..6).map(|i| (2 * i) as f64 * (11 - i) as f64).sum();
+        assert_eq!(e, dot_slice(&even, &back));
+        assert_eq!(e, dot_slice(&back, &even));
+        assert_eq!(
+            dot_slice(&data[..5], &data[3..8]),
+            dot_slice(&StridedSlice::from(&data[..5]), &data[3..8])
+        );
+
+        assert_eq!(vec![0.0, 3.0, 6.0], add_slice(&even, &back.rev())[..3]);
+        assert_eq!(vec![0.0, 20.0, 36.0], mul_slice(&even, &back)[..3]);
+        let mut out = [0.0; 4];
+        assert_eq!(4, sub_slice_into(&back, &[1.0; 4], &mut out));
+        assert_eq!([10.0, 9.0, 8.0, 7.0], out);
+        let mut t = vec![1.0; 3];
+        assert_eq!(3, max_assign_slice(&mut t, &even));
+        assert_eq!(vec![1.0, 2.0, 4.0], t);
+    }
+
+    #[test]
+    fn turbofish_check() {
+        let t = vec![1.0, 2.0, 3.0];
+        let u = [4.0, 5.0, 6.0];
+        assert_eq!(32.0, dot_slice::<f64>(&t, &u));
+        assert_eq!(vec![4, 10], mul_slice::<i32>(&[1, 2], &[4, 5]));
+        assert_eq!(vec![2.0, 4.0, 6.0], mul_scalar::<f64>(&t, 2.0));
+        let mut out = [0u8; 2];
+        assert_eq!(2, add_slice_into::<u8>(&[1, 2], &3u8, &mut out));
+    }
+
+    #[test]
+    fn broadcast_check() {
+        let v = vec![1.0, 2.0, 3.0];
+        assert_eq!(vec![2.0, 4.0, 6.0], mul_slice(&v, &2.0));
+        assert_eq!(vec![2.0, 4.0, 6.0], mul_slice(&2.0, &v));
+        assert_eq!(vec![-1.0, -2.0, -3.0], sub_slice(&0.0, &v));
+        assert_eq!(vec![5.0], add_slice(&2.0, &3.0));
+        assert_eq!(vec![1.0, 2.0, 2.0], min_slice(&v, &2.0));
+
+        let mut out = [0.0; 2];
+        assert_eq!(2, div_slice_into(&v, &Scalar(2.0), &mut out));
+        assert_eq!([0.5, 1.0], out);
+        let mut t = [1u8, 2, 3];
+        assert_eq!(3, mul_assign_slice(&mut t, &3u8));
+        assert_eq!([3, 6, 9], t);
+        assert_eq!(vec![true, false], xor_slice(&[false, true], &true));
+
+        let s = StridedSlice::new(&v, 2, -2).unwrap();
+        assert_eq!(vec![13.0, 11.0], add_scalar(&s, 10.0));
+        assert_eq!(mul_slice(&v, &vec![0.5; 3]), mul_scalar(&v, 0.5));
+        assert_eq!(vec![4, 4], div_scalar(&[8, 9], 2));
+    }
 }
@@ -0,0 +1,681 @@
+//! Linear algebra on square (and tall) matrices stored as flat
+//! row-major slices, the same layout `MatrixView::row_major()` reads.
+//!
+//! Each decomposition comes as a struct you build once and reuse:
+//! `Lu` (partial pivoting), `Qr` (Householder) and `Cholesky`. The free
+//! functions `solve()`, `inverse()`, `determinant()` and
+//! `least_squares()` are shortcuts over them.
+//!
+//! For spectra there's `SymmetricEigen` (cyclic Jacobi) and `Svd`
+//! (Golub–Kahan), with `rank()`, `condition_number()` and
+//! `pseudo_inverse()` on top of the SVD.
+//!
+//! A pivot that's zero, or small enough next to the rest of the matrix
+//! that dividing by it would just produce noise, gets reported as
+//! `LinalgError::SingularMatrix` instead of quietly filling your
+//! answer with NaNs and infinities.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::dot_slice;
+use crate::matrix::ShapeError;
+use crate::num::Float;
+
+mod eigen;
+mod svd;
+
+pub use eigen::SymmetricEigen;
+pub use svd::{condition_number, pseudo_inverse, rank, Svd};
+
+/// # LinalgError
+/// What can go wrong solving a linear system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinalgError {
+    /// The inputs don't have the shapes the routine needs.
+    Shape(ShapeError),
+    /// The matrix is singular (or rank deficient) to working precision.
+    SingularMatrix,
+    /// Cholesky ran into a non-positive pivot.
+    NotPositiveDefinite,
+    /// An iterative routine (eigenvalues, SVD, polynomial roots) ran
+    /// out of iterations.
+    NoConvergence,
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinalgError::Shape(e) => e.fmt(f),
+            LinalgError::SingularMatrix => write!(f, "matrix is singular"),
+            LinalgError::NotPositiveDefinite => {
+                write!(f, "matrix is not symmetric positive definite")
+            }
+            LinalgError::NoConvergence => write!(f, "iteration didn't converge"),
+        }
+    }
+}
+
+impl Error for LinalgError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LinalgError::Shape(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ShapeError> for LinalgError {
+    fn from(e: ShapeError) -> Self {
+        LinalgError::Shape(e)
+    }
+}
+
+fn check_len(len: usize, rows: usize, cols: usize) -> Result<(), LinalgError> {
+    if rows.checked_mul(cols) != Some(len) {
+        return Err(ShapeError::BufferSize {
+            needed: rows.saturating_mul(cols),
+            got: len,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+fn check_rhs(rows: usize, cols: usize, b: &[impl Sized]) -> Result<(), LinalgError> {
+    if b.len() != rows {
+        return Err(ShapeError::Mismatch {
+            left: (rows, cols),
+            right: (b.len(), 1),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+fn max_abs<T: Float>(a: &[T]) -> T {
+    a.iter()
+        .fold(T::ZERO, |m, x| if x.abs() > m { x.abs() } else { m })
+}
+
+/// Anything at or below this is treated as a zero pivot.
+fn tolerance<T: Float>(a: &[T], n: usize) -> T {
+    T::EPSILON * T::from_f64(n as f64) * max_abs(a)
+}
+
+/// # Lu
+/// LU decomposition with partial pivoting:
+/// `P·A = L·U`, with `L` unit lower triangular and
+/// `U` upper triangular.
+///
+/// Factoring never fails on a singular matrix (its
+/// determinant is a perfectly good zero); `solve()`
+/// and `inverse()` are where you get told.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::Lu;
+///
+/// let a = [2.0, 1.0, 4.0, 3.0];
+/// let lu = Lu::new(&a, 2).unwrap();
+/// assert_eq!(2.0, lu.determinant());
+/// assert_eq!(Ok(vec![1.0, 1.0]), lu.solve(&[3.0, 7.0]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lu<T> {
+    lu: Vec<T>,
+    n: usize,
+    perm: Vec<usize>,
+    odd: bool,
+    tol: T,
+}
+
+impl<T: Float> Lu<T> {
+    /// Factors the `n` x `n` row-major matrix `a`.
+    pub fn new(a: &[T], n: usize) -> Result<Self, LinalgError> {
+        check_len(a.len(), n, n)?;
+        let mut lu = a.to_vec();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut odd = false;
+
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| {
+                    let (x, y) = (lu[i * n + k].abs(), lu[j * n + k].abs());
+                    x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap_or(k);
+            if p != k {
+                for j in 0..n {
+                    lu.swap(p * n + j, k * n + j);
+                }
+                perm.swap(p, k);
+                odd = !odd;
+            }
+            let pivot = lu[k * n + k];
+            if pivot == T::ZERO {
+                continue;
+            }
+            for i in k + 1..n {
+                let f = lu[i * n + k] / pivot;
+                lu[i * n + k] = f;
+                for j in k + 1..n {
+                    lu[i * n + j] = lu[i * n + j] - f * lu[k * n + j];
+                }
+            }
+        }
+        Ok(Lu {
+            tol: tolerance(a, n),
+            lu,
+            n,
+            perm,
+            odd,
+        })
+    }
+
+    /// Does `U` have a pivot too small to divide by?
+    pub fn is_singular(&self) -> bool {
+        (0..self.n).any(|k| self.lu[k * self.n + k].abs() <= self.tol)
+    }
+
+    /// Product of the pivots, sign flipped for every row swap.
+    pub fn determinant(&self) -> T {
+        let d = (0..self.n).fold(T::ONE, |d, k| d * self.lu[k * self.n + k]);
+        if self.odd {
+            -d
+        } else {
+            d
+        }
+    }
+
+    /// Solves `A·x = b`.
+    pub fn solve(&self, b: &[T]) -> Result<Vec<T>, LinalgError> {
+        check_rhs(self.n, self.n, b)?;
+        if self.is_singular() {
+            return Err(LinalgError::SingularMatrix);
+        }
+        let n = self.n;
+        let mut x: Vec<T> = self.perm.iter().map(|&p| b[p]).collect();
+        for i in 0..n {
+            x[i] = x[i] - dot_slice(&self.lu[i * n..i * n + i], &x[..i]);
+        }
+        for i in (0..n).rev() {
+            let s = dot_slice(&self.lu[i * n + i + 1..(i + 1) * n], &x[i + 1..]);
+            x[i] = (x[i] - s) / self.lu[i * n + i];
+        }
+        Ok(x)
+    }
+
+    /// `A⁻¹` as a row-major Vec.
+    pub fn inverse(&self) -> Result<Vec<T>, LinalgError> {
+        let n = self.n;
+        let mut inv = vec![T::ZERO; n * n];
+        let mut e = vec![T::ZERO; n];
+        for j in 0..n {
+            e[j] = T::ONE;
+            for (i, v) in self.solve(&e)?.into_iter().enumerate() {
+                inv[i * n + j] = v;
+            }
+            e[j] = T::ZERO;
+        }
+        Ok(inv)
+    }
+
+    /// The unit lower triangle `L`, row-major.
+    pub fn l(&self) -> Vec<T> {
+        let n = self.n;
+        let mut l = vec![T::ZERO; n * n];
+        for i in 0..n {
+            l[i * n..i * n + i].copy_from_slice(&self.lu[i * n..i * n + i]);
+            l[i * n + i] = T::ONE;
+        }
+        l
+    }
+
+    /// The upper triangle `U`, row-major.
+    pub fn u(&self) -> Vec<T> {
+        let n = self.n;
+        let mut u = vec![T::ZERO; n * n];
+        for i in 0..n {
+            u[i * n + i..(i + 1) * n].copy_from_slice(&self.lu[i * n + i..(i + 1) * n]);
+        }
+        u
+    }
+
+    /// Row `i` of `P·A` is row `permutation()[i]` of `A`.
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+}
+
+/// # Qr
+/// Householder QR decomposition of a `rows` x `cols`
+/// matrix: `A = Q·R`, `Q` with orthonormal columns and
+/// `R` upper triangular.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::Qr;
+///
+/// // Fit y = c0 + c1·x through (0, 1), (1, 3), (2, 5).
+/// let a = [1.0f64, 0.0, 1.0, 1.0, 1.0, 2.0];
+/// let qr = Qr::new(&a, 3, 2).unwrap();
+/// let c = qr.solve_least_squares(&[1.0, 3.0, 5.0]).unwrap();
+/// assert!((c[0] - 1.0).abs() < 1e-12 && (c[1] - 2.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Qr<T> {
+    qr: Vec<T>,
+    rows: usize,
+    cols: usize,
+    rdiag: Vec<T>,
+    tol: T,
+}
+
+impl<T: Float> Qr<T> {
+    /// Factors the `rows` x `cols` row-major matrix `a`.
+    pub fn new(a: &[T], rows: usize, cols: usize) -> Result<Self, LinalgError> {
+        check_len(a.len(), rows, cols)?;
+        let (m, n) = (rows, cols);
+        let mut qr = a.to_vec();
+        let mut rdiag = vec![T::ZERO; n];
+        let mut col = Vec::with_capacity(m);
+
+        for k in 0..n.min(m) {
+            col.clear();
+            col.extend((k..m).map(|i| qr[i * n + k]));
+            let mut nrm = crate::norm::l2_norm(&col);
+            if nrm != T::ZERO {
+                if qr[k * n + k] < T::ZERO {
+                    nrm = -nrm;
+                }
+                for i in k..m {
+                    qr[i * n + k] = qr[i * n + k] / nrm;
+                }
+                qr[k * n + k] = qr[k * n + k] + T::ONE;
+                for j in k + 1..n {
+                    let s = (k..m).fold(T::ZERO, |s, i| s + qr[i * n + k] * qr[i * n + j]);
+                    let s = -s / qr[k * n + k];
+                    for i in k..m {
+                        qr[i * n + j] = qr[i * n + j] + s * qr[i * n + k];
+                    }
+                }
+            }
+            rdiag[k] = -nrm;
+        }
+        Ok(Qr {
+            tol: tolerance(a, m.max(n)),
+            qr,
+            rows,
+            cols,
+            rdiag,
+        })
+    }
+
+    /// Are the columns linearly independent (to working precision)?
+    pub fn is_full_rank(&self) -> bool {
+        self.rows >= self.cols && self.rdiag.iter().all(|d| d.abs() > self.tol)
+    }
+
+    /// The upper triangle `R`, `cols` x `cols` row-major
+    /// (`min(rows, cols)` x `cols` if the matrix is wide).
+    pub fn r(&self) -> Vec<T> {
+        let (m, n) = (self.rows, self.cols);
+        let k = m.min(n);
+        let mut r = vec![T::ZERO; k * n];
+        for i in 0..k {
+            r[i * n + i] = self.rdiag[i];
+            for j in i + 1..n {
+                r[i * n + j] = self.qr[i * n + j];
+            }
+        }
+        r
+    }
+
+    /// The thin `Q`, `rows` x `min(rows, cols)` row-major.
+    pub fn q(&self) -> Vec<T> {
+        let (m, n) = (self.rows, self.cols);
+        let k = m.min(n);
+        let mut q = vec![T::ZERO; m * k];
+        for c in (0..k).rev() {
+            q[c * k + c] = T::ONE;
+            for j in c..k {
+                if self.qr[c * n + c] != T::ZERO {
+                    let s = (c..m).fold(T::ZERO, |s, i| s + self.qr[i * n + c] * q[i * k + j]);
+                    let s = -s / self.qr[c * n + c];
+                    for i in c..m {
+                        q[i * k + j] = q[i * k + j] + s * self.qr[i * n + c];
+                    }
+                }
+            }
+        }
+        q
+    }
+
+    /// The `x` minimising `‖A·x - b‖₂`. Needs `rows >= cols`
+    /// and a full rank matrix; for a square one that's
+    /// just the solution of `A·x = b`.
+    pub fn solve_least_squares(&self, b: &[T]) -> Result<Vec<T>, LinalgError> {
+        let (m, n) = (self.rows, self.cols);
+        check_rhs(m, n, b)?;
+        if m < n {
+            return Err(ShapeError::Mismatch {
+                left: (m, n),
+                right: (b.len(), 1),
+            }
+            .into());
+        }
+        if !self.is_full_rank() {
+            return Err(LinalgError::SingularMatrix);
+        }
+        let mut y = b.to_vec();
+        for k in 0..n {
+            let s = (k..m).fold(T::ZERO, |s, i| s + self.qr[i * n + k] * y[i]);
+            let s = -s / self.qr[k * n + k];
+            for (i, yi) in y.iter_mut().enumerate().skip(k) {
+                *yi = *yi + s * self.qr[i * n + k];
+            }
+        }
+        let mut x = y[..n].to_vec();
+        for k in (0..n).rev() {
+            let s = dot_slice(&self.qr[k * n + k + 1..(k + 1) * n], &x[k + 1..]);
+            x[k] = (x[k] - s) / self.rdiag[k];
+        }
+        Ok(x)
+    }
+}
+
+/// # Cholesky
+/// Cholesky decomposition of a symmetric positive
+/// definite matrix: `A = L·Lᵀ`. Only the lower
+/// triangle of `A` gets read.
+///
+/// About twice as fast as `Lu` and needs no
+/// pivoting, if your matrix qualifies.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::{Cholesky, LinalgError};
+///
+/// let a = [4.0, 2.0, 2.0, 3.0];
+/// let c = Cholesky::new(&a, 2).unwrap();
+/// assert_eq!(vec![2.0, 0.0, 1.0, 2.0_f64.sqrt()], c.l());
+///
+/// let e = Cholesky::new(&[1.0, 2.0, 2.0, 1.0], 2);
+/// assert_eq!(Err(LinalgError::NotPositiveDefinite), e.map(|_| ()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cholesky<T> {
+    l: Vec<T>,
+    n: usize,
+}
+
+impl<T: Float> Cholesky<T> {
+    /// Factors the `n` x `n` row-major matrix `a`.
+    pub fn new(a: &[T], n: usize) -> Result<Self, LinalgError> {
+        check_len(a.len(), n, n)?;
+        let tol = tolerance(a, n);
+        let mut l = vec![T::ZERO; n * n];
+        for i in 0..n {
+            for j in 0..=i {
+                let s = dot_slice(&l[i * n..i * n + j], &l[j * n..j * n + j]);
+                let v = a[i * n + j] - s;
+                if i == j {
+                    if v.is_nan() || v <= tol {
+                        return Err(LinalgError::NotPositiveDefinite);
+                    }
+                    l[i * n + i] = v.sqrt();
+                } else {
+                    l[i * n + j] = v / l[j * n + j];
+                }
+            }
+        }
+        Ok(Cholesky { l, n })
+    }
+
+    /// The lower triangle `L`, row-major.
+    pub fn l(&self) -> Vec<T> {
+        self.l.clone()
+    }
+
+    /// Solves `A·x = b`.
+    pub fn solve(&self, b: &[T]) -> Result<Vec<T>, LinalgError> {
+        check_rhs(self.n, self.n, b)?;
+        let n = self.n;
+        let l = &self.l;
+        let mut x = b.to_vec();
+        for i in 0..n {
+            x[i] = (x[i] - dot_slice(&l[i * n..i * n + i], &x[..i])) / l[i * n + i];
+        }
+        for i in (0..n).rev() {
+            let s = (i + 1..n).fold(T::ZERO, |s, k| s + l[k * n + i] * x[k]);
+            x[i] = (x[i] - s) / l[i * n + i];
+        }
+        Ok(x)
+    }
+
+    /// `det(A)`, the product of `L`'s diagonal squared.
+    pub fn determinant(&self) -> T {
+        (0..self.n).fold(T::ONE, |d, i| {
+            let v = self.l[i * self.n + i];
+            d * v * v
+        })
+    }
+}
+
+/// # solve()
+/// Takes an `n` x `n` row-major matrix and a
+/// right hand side.
+///
+/// Returns the `x` with `A·x = b`, by LU with
+/// partial pivoting, or `SingularMatrix`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::{solve, LinalgError};
+///
+/// let a = [2.0, 1.0, 1.0, 3.0];
+/// assert_eq!(Ok(vec![1.0, 1.0]), solve(&a, 2, &[3.0, 4.0]));
+/// let s = [1.0, 2.0, 2.0, 4.0];
+/// assert_eq!(Err(LinalgError::SingularMatrix), solve(&s, 2, &[3.0, 6.0]));
+/// ```
+pub fn solve<T: Float>(a: &[T], n: usize, b: &[T]) -> Result<Vec<T>, LinalgError> {
+    Lu::new(a, n)?.solve(b)
+}
+
+/// # inverse()
+/// Takes an `n` x `n` row-major matrix.
+///
+/// Returns its inverse, row-major, or
+/// `SingularMatrix` if it hasn't got one.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::inverse;
+///
+/// let a = [4.0f64, 7.0, 2.0, 6.0];
+/// let q = inverse(&a, 2).unwrap();
+/// let e = [0.6, -0.7, -0.2, 0.4];
+/// assert!(q.iter().zip(e).all(|(q, e)| (q - e).abs() < 1e-12));
+/// ```
+pub fn inverse<T: Float>(a: &[T], n: usize) -> Result<Vec<T>, LinalgError> {
+    Lu::new(a, n)?.inverse()
+}
+
+/// # determinant()
+/// Takes an `n` x `n` row-major matrix.
+///
+/// Returns its determinant, by LU with partial
+/// pivoting. A singular matrix just gives (about)
+/// zero. The empty matrix has determinant one.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::determinant;
+///
+/// let a = [2.0f64, 0.0, 1.0, 1.0, 3.0, 2.0, 1.0, 1.0, 2.0];
+/// assert!((determinant(&a, 3).unwrap() - 6.0).abs() < 1e-12);
+/// ```
+pub fn determinant<T: Float>(a: &[T], n: usize) -> Result<T, LinalgError> {
+    Ok(Lu::new(a, n)?.determinant())
+}
+
+/// # least_squares()
+/// Takes a `rows` x `cols` row-major matrix (with
+/// `rows >= cols`) and a right hand side.
+///
+/// Returns the `x` minimising `‖A·x - b‖₂`, by
+/// Householder QR, or `SingularMatrix` if the
+/// columns aren't independent.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::least_squares;
+///
+/// // Best line through (0, 0), (1, 1), (2, 1), (3, 2).
+/// let a = [1.0f64, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0];
+/// let c = least_squares(&a, 4, 2, &[0.0, 1.0, 1.0, 2.0]).unwrap();
+/// assert!((c[0] - 0.1).abs() < 1e-12 && (c[1] - 0.6).abs() < 1e-12);
+/// ```
+pub fn least_squares<T: Float>(
+    a: &[T],
+    rows: usize,
+    cols: usize,
+    b: &[T],
+) -> Result<Vec<T>, LinalgError> {
+    Qr::new(a, rows, cols)?.solve_least_squares(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{matmul, MatrixView};
+    use crate::test_util::close;
+
+    fn mul(a: &[f64], b: &[f64], m: usize, k: usize, n: usize) -> Vec<f64> {
+        let a = MatrixView::row_major(a, m, k).unwrap();
+        let b = MatrixView::row_major(b, k, n).unwrap();
+        matmul(&a, &b).unwrap()
+    }
+
+    /// Hilbert-ish but well conditioned: diagonally dominant 5x5.
+    fn sample() -> Vec<f64> {
+        (0..25)
+            .map(|k| {
+                let (i, j) = (k / 5, k % 5);
+                if i == j {
+                    10.0 + i as f64
+                } else {
+                    1.0 / (i + j + 1) as f64
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lu_check() {
+        let a = sample();
+        let lu = Lu::new(&a, 5).unwrap();
+        let pa: Vec<f64> = lu
+            .permutation()
+            .iter()
+            .flat_map(|&p| a[p * 5..p * 5 + 5].to_vec())
+            .collect();
+        assert!(close(&pa, &mul(&lu.l(), &lu.u(), 5, 5, 5), 1e-12));
+
+        let x = [1.0, -2.0, 3.0, -4.0, 5.0];
+        let b = mul(&a, &x, 5, 5, 1);
+        assert!(close(&x, &lu.solve(&b).unwrap(), 1e-12));
+
+        let inv = lu.inverse().unwrap();
+        let id: Vec<f64> = (0..25).map(|k| (k % 6 == 0) as u8 as f64).collect();
+        assert!(close(&id, &mul(&a, &inv, 5, 5, 5), 1e-12));
+    }
+
+    #[test]
+    fn pivoting_check() {
+        // Zero in the top left: fails without row swaps.
+        let a = [0.0, 1.0, 1.0, 1.0];
+        assert_eq!(Ok(vec![1.0, 2.0]), solve(&a, 2, &[2.0, 3.0]));
+        assert_eq!(Ok(-1.0), determinant(&a, 2));
+    }
+
+    #[test]
+    fn singular_check() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+        assert_eq!(Err(LinalgError::SingularMatrix), inverse(&a, 3));
+        assert!(determinant(&a, 3).unwrap().abs() < 1e-12);
+        let q = Qr::new(&a, 3, 3).unwrap();
+        assert!(!q.is_full_rank());
+        assert_eq!(
+            Err(LinalgError::SingularMatrix),
+            q.solve_least_squares(&[1.0, 2.0, 3.0])
+        );
+        assert_eq!(Ok(1.0), determinant::<f64>(&[], 0));
+    }
+
+    #[test]
+    fn shape_check() {
+        let e = LinalgError::Shape(ShapeError::BufferSize { needed: 4, got: 3 });
+        assert_eq!(Err(e), solve(&[1.0, 2.0, 3.0], 2, &[1.0, 1.0]));
+        assert!(solve(&[1.0, 0.0, 0.0, 1.0], 2, &[1.0]).is_err());
+        assert!(least_squares(&[1.0, 2.0], 1, 2, &[1.0]).is_err());
+        assert_eq!(
+            "matrix is singular",
+            LinalgError::SingularMatrix.to_string()
+        );
+    }
+
+    #[test]
+    fn qr_check() {
+        let a: Vec<f64> = (0..12)
+            .map(|k| ((k * 7) % 5) as f64 + (k / 4) as f64)
+            .collect();
+        let qr = Qr::new(&a, 4, 3).unwrap();
+        let (q, r) = (qr.q(), qr.r());
+        assert!(close(&a, &mul(&q, &r, 4, 3, 3), 1e-12));
+        let qt = MatrixView::row_major(&q, 4, 3)
+            .unwrap()
+            .transpose()
+            .to_vec();
+        let id = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        assert!(close(&id, &mul(&qt, &q, 3, 4, 3), 1e-12));
+
+        // Square and full rank: least squares is the exact solution.
+        let s = sample();
+        let x = [2.0, 0.0, -1.0, 0.5, 3.0];
+        let b = mul(&s, &x, 5, 5, 1);
+        assert!(close(&x, &least_squares(&s, 5, 5, &b).unwrap(), 1e-12));
+    }
+
+    #[test]
+    fn cholesky_check() {
+        let s = sample();
+        let st = MatrixView::row_major(&s, 5, 5)
+            .unwrap()
+            .transpose()
+            .to_vec();
+        let spd = mul(&s, &st, 5, 5, 5);
+        let c = Cholesky::new(&spd, 5).unwrap();
+        let l = c.l();
+        let lt = MatrixView::row_major(&l, 5, 5)
+            .unwrap()
+            .transpose()
+            .to_vec();
+        assert!(close(&spd, &mul(&l, &lt, 5, 5, 5), 1e-9));
+
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = mul(&spd, &x, 5, 5, 1);
+        assert!(close(&x, &c.solve(&b).unwrap(), 1e-12));
+        let d = determinant(&spd, 5).unwrap();
+        assert!((c.determinant() - d).abs() / d < 1e-12);
+    }
+
+    #[test]
+    fn f32_check() {
+        let a = [3.0f32, 1.0, 1.0, 2.0];
+        let x = solve(&a, 2, &[9.0, 8.0]).unwrap();
+        assert!((x[0] - 2.0).abs() < 1e-6 && (x[1] - 3.0).abs() < 1e-6);
+        assert!(Cholesky::new(&a, 2).is_ok());
+    }
+}
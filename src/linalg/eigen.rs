@@ -0,0 +1,199 @@
+use super::{check_len, LinalgError};
+use crate::norm::l2_norm;
+use crate::num::Float;
+
+/// Sweeps before giving up; Jacobi converges quadratically,
+/// so anything sane is done in well under twenty.
+const MAX_SWEEPS: usize = 64;
+
+/// # SymmetricEigen
+/// Eigen-decomposition of a real symmetric `n` x `n`
+/// matrix by cyclic Jacobi rotations:
+/// `A = V·diag(λ)·Vᵀ`, `V` orthogonal.
+///
+/// Only the lower triangle of `A` gets read. Eigenvalues
+/// come largest first, the order PCA wants them in, and
+/// column `j` of `vectors()` goes with `values()[j]`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::SymmetricEigen;
+///
+/// let a = [2.0f64, 1.0, 1.0, 2.0];
+/// let e = SymmetricEigen::new(&a, 2).unwrap();
+/// assert!((e.values()[0] - 3.0).abs() < 1e-12);
+/// assert!((e.values()[1] - 1.0).abs() < 1e-12);
+/// let v = e.vector(0);
+/// assert!((v[0].abs() - 0.5f64.sqrt()).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymmetricEigen<T> {
+    values: Vec<T>,
+    vectors: Vec<T>,
+    n: usize,
+}
+
+impl<T: Float> SymmetricEigen<T> {
+    /// Decomposes the `n` x `n` row-major matrix `a`.
+    pub fn new(a: &[T], n: usize) -> Result<Self, LinalgError> {
+        check_len(a.len(), n, n)?;
+        let mut a = a.to_vec();
+        for i in 0..n {
+            for j in i + 1..n {
+                a[i * n + j] = a[j * n + i];
+            }
+        }
+        let mut v = vec![T::ZERO; n * n];
+        for i in 0..n {
+            v[i * n + i] = T::ONE;
+        }
+
+        // Both norms scaled, so entries past ~1e154 don't square to inf.
+        let scale = l2_norm(&a);
+        let two = T::ONE + T::ONE;
+        let mut converged = false;
+        for _ in 0..MAX_SWEEPS {
+            let off = (0..n).fold(T::ZERO, |s, i| {
+                s.hypot(l2_norm(&a[i * n + i + 1..(i + 1) * n]))
+            });
+            if off <= T::EPSILON * scale {
+                converged = true;
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    let apq = a[p * n + q];
+                    if apq == T::ZERO {
+                        continue;
+                    }
+                    let theta = (a[q * n + q] - a[p * n + p]) / (two * apq);
+                    let t = T::ONE / (theta.abs() + theta.hypot(T::ONE));
+                    let t = if theta < T::ZERO { -t } else { t };
+                    let c = T::ONE / (t * t + T::ONE).sqrt();
+                    let s = t * c;
+                    for k in 0..n {
+                        let (x, y) = (a[k * n + p], a[k * n + q]);
+                        a[k * n + p] = c * x - s * y;
+                        a[k * n + q] = s * x + c * y;
+                    }
+                    for k in 0..n {
+                        let (x, y) = (a[p * n + k], a[q * n + k]);
+                        a[p * n + k] = c * x - s * y;
+                        a[q * n + k] = s * x + c * y;
+                    }
+                    for k in 0..n {
+                        let (x, y) = (v[k * n + p], v[k * n + q]);
+                        v[k * n + p] = c * x - s * y;
+                        v[k * n + q] = s * x + c * y;
+                    }
+                }
+            }
+        }
+        if !converged {
+            return Err(LinalgError::NoConvergence);
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| {
+            a[j * n + j]
+                .partial_cmp(&a[i * n + i])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let values = order.iter().map(|&i| a[i * n + i]).collect();
+        let mut vectors = vec![T::ZERO; n * n];
+        for (j, &src) in order.iter().enumerate() {
+            for k in 0..n {
+                vectors[k * n + j] = v[k * n + src];
+            }
+        }
+        Ok(SymmetricEigen { values, vectors, n })
+    }
+
+    /// The eigenvalues, largest first.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The eigenvectors as the columns of an `n` x `n`
+    /// row-major matrix.
+    pub fn vectors(&self) -> &[T] {
+        &self.vectors
+    }
+
+    /// The unit eigenvector for `values()[j]`.
+    pub fn vector(&self, j: usize) -> Vec<T> {
+        (0..self.n).map(|k| self.vectors[k * self.n + j]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{matmul, MatrixView};
+    use crate::test_util::close;
+
+    #[test]
+    fn reference_check() {
+        // The 1D Laplacian: eigenvalues 2 - 2·cos(kπ/4).
+        let a = [2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0];
+        let e = SymmetricEigen::new(&a, 3).unwrap();
+        let r = 2.0f64.sqrt();
+        assert!(close(&[2.0 + r, 2.0, 2.0 - r], e.values(), 1e-12));
+        let v = e.vector(1);
+        assert!(
+            close(&[r / 2.0, 0.0, -r / 2.0], &v, 1e-12)
+                || close(&[-r / 2.0, 0.0, r / 2.0], &v, 1e-12)
+        );
+    }
+
+    #[test]
+    fn reconstruct_check() {
+        let a: Vec<f64> = (0..16)
+            .map(|k| {
+                let (i, j) = (k / 4, k % 4);
+                1.0 / (i + j + 1) as f64
+            })
+            .collect();
+        let e = SymmetricEigen::new(&a, 4).unwrap();
+        let v = MatrixView::row_major(e.vectors(), 4, 4).unwrap();
+        let id = matmul(&v.transpose(), &v).unwrap();
+        let eye: Vec<f64> = (0..16).map(|k| (k % 5 == 0) as u8 as f64).collect();
+        assert!(close(&eye, &id, 1e-12));
+
+        let mut vl = e.vectors().to_vec();
+        for (k, x) in vl.iter_mut().enumerate() {
+            *x *= e.values()[k % 4];
+        }
+        let vl = MatrixView::row_major(&vl, 4, 4).unwrap();
+        assert!(close(&a, &matmul(&vl, &v.transpose()).unwrap(), 1e-12));
+        // Hilbert 4x4 reference spectrum.
+        let h = [
+            1.500_214_280_06,
+            0.169_141_220_22,
+            0.006_738_273_61,
+            0.000_096_702_30,
+        ];
+        assert!(close(&h, e.values(), 1e-10));
+    }
+
+    #[test]
+    fn edge_check() {
+        let e = SymmetricEigen::<f64>::new(&[], 0).unwrap();
+        assert!(e.values().is_empty());
+        let d = SymmetricEigen::new(&[1.0, 0.0, 0.0, 5.0], 2).unwrap();
+        assert_eq!(&[5.0, 1.0], d.values());
+        assert_eq!(&[0.0, 1.0, 1.0, 0.0], d.vectors());
+        assert!(SymmetricEigen::new(&[1.0, 2.0], 2).is_err());
+    }
+
+    #[test]
+    fn huge_check() {
+        // Squares of these overflow; the rotations still have to happen.
+        let e = SymmetricEigen::new(&[1e200, 1e200, 1e200, 1e200], 2).unwrap();
+        assert!((e.values()[0] / 2e200 - 1.0).abs() < 1e-12);
+        assert!(e.values()[1].abs() < 1e188);
+        let e = SymmetricEigen::new(&[3e300, 1e300, 1e300, 3e300], 2).unwrap();
+        assert!((e.values()[0] / 4e300 - 1.0).abs() < 1e-12);
+        assert!((e.values()[1] / 2e300 - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,606 @@
+use super::{check_len, LinalgError};
+use crate::dot_slice;
+use crate::num::Float;
+
+/// QR sweeps per singular value before giving up.
+const MAX_ITER: usize = 75;
+
+/// # Svd
+/// Singular value decomposition of a `rows` x `cols`
+/// matrix: `A = U·diag(σ)·Vᵀ`, by Golub–Kahan
+/// bidiagonalisation and implicit shifted QR (the
+/// JAMA / LINPACK algorithm).
+///
+/// It's the thin version: with `k = min(rows, cols)`,
+/// `U` is `rows` x `k`, `V` is `cols` x `k` and there
+/// are `k` singular values, largest first.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::Svd;
+///
+/// let a = [3.0f64, 2.0, 2.0, 2.0, 3.0, -2.0];
+/// let svd = Svd::new(&a, 2, 3).unwrap();
+/// let s = svd.singular_values();
+/// assert!((s[0] - 5.0).abs() < 1e-12 && (s[1] - 3.0).abs() < 1e-12);
+/// assert_eq!(2, svd.rank());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svd<T> {
+    u: Vec<T>,
+    s: Vec<T>,
+    v: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T: Float> Svd<T> {
+    /// Decomposes the `rows` x `cols` row-major matrix `a`.
+    pub fn new(a: &[T], rows: usize, cols: usize) -> Result<Self, LinalgError> {
+        check_len(a.len(), rows, cols)?;
+        // The work happens column-major on a tall matrix, so each
+        // column is a contiguous run for dot_slice(). A wide `a`'s
+        // row-major buffer already is its transpose column-major.
+        let wide = rows < cols;
+        let (m, n) = if wide { (cols, rows) } else { (rows, cols) };
+        let work = if wide {
+            a.to_vec()
+        } else {
+            let mut t = vec![T::ZERO; m * n];
+            for i in 0..m {
+                for j in 0..n {
+                    t[i + j * m] = a[i * n + j];
+                }
+            }
+            t
+        };
+        let (u, s, v) = golub_kahan(work, m, n)?;
+        // `u` is m x n and `v` n x n, both column-major; swap them
+        // back for a wide matrix and hand out row-major.
+        let (u, v) = if wide { (v, u) } else { (u, v) };
+        let k = n;
+        let to_rows = |c: Vec<T>, r: usize| {
+            let mut out = vec![T::ZERO; r * k];
+            for i in 0..r {
+                for j in 0..k {
+                    out[i * k + j] = c[i + j * r];
+                }
+            }
+            out
+        };
+        Ok(Svd {
+            u: to_rows(u, rows),
+            s,
+            v: to_rows(v, cols),
+            rows,
+            cols,
+        })
+    }
+
+    /// The singular values, largest first.
+    pub fn singular_values(&self) -> &[T] {
+        &self.s
+    }
+
+    /// The left singular vectors as the columns of a
+    /// `rows` x `k` row-major matrix.
+    pub fn u(&self) -> &[T] {
+        &self.u
+    }
+
+    /// The right singular vectors as the columns of a
+    /// `cols` x `k` row-major matrix.
+    pub fn v(&self) -> &[T] {
+        &self.v
+    }
+
+    /// Singular values at or below this count as zero:
+    /// `max(rows, cols) · σ₁ · ε`, same as NumPy and MATLAB.
+    pub fn tolerance(&self) -> T {
+        let top = self.s.first().copied().unwrap_or(T::ZERO);
+        T::from_f64(self.rows.max(self.cols) as f64) * top * T::EPSILON
+    }
+
+    /// How many singular values are above `tolerance()`.
+    pub fn rank(&self) -> usize {
+        let tol = self.tolerance();
+        self.s.iter().filter(|&&x| x > tol).count()
+    }
+
+    /// `σ₁ / σₖ` in the 2-norm. Infinite for a singular
+    /// matrix, one for an empty one.
+    pub fn condition_number(&self) -> T {
+        match (self.s.first(), self.s.last()) {
+            (Some(_), Some(&lo)) if lo == T::ZERO => T::INFINITY,
+            (Some(&hi), Some(&lo)) => hi / lo,
+            _ => T::ONE,
+        }
+    }
+
+    /// The Moore–Penrose pseudo-inverse `V·diag(1/σ)·Uᵀ`,
+    /// `cols` x `rows` row-major, dropping singular values
+    /// at or below `tolerance()`.
+    pub fn pseudo_inverse(&self) -> Vec<T> {
+        let (m, n, k) = (self.rows, self.cols, self.s.len());
+        let tol = self.tolerance();
+        let inv: Vec<T> = self
+            .s
+            .iter()
+            .map(|&x| if x > tol { T::ONE / x } else { T::ZERO })
+            .collect();
+        let mut vs = self.v.clone();
+        for (idx, x) in vs.iter_mut().enumerate() {
+            *x = *x * inv[idx % k.max(1)];
+        }
+        let mut out = vec![T::ZERO; n * m];
+        for i in 0..n {
+            for j in 0..m {
+                out[i * m + j] = dot_slice(&vs[i * k..(i + 1) * k], &self.u[j * k..(j + 1) * k]);
+            }
+        }
+        out
+    }
+}
+
+/// Plane rotation of columns `j` and `k` (each `len` long) in a
+/// column-major buffer: `(x, y) -> (c·x + s·y, c·y - s·x)`.
+fn rotate<T: Float>(buf: &mut [T], len: usize, j: usize, k: usize, c: T, s: T) {
+    for i in 0..len {
+        let (x, y) = (buf[i + j * len], buf[i + k * len]);
+        buf[i + j * len] = c * x + s * y;
+        buf[i + k * len] = c * y - s * x;
+    }
+}
+
+/// The SVD proper, on a column-major `m` x `n` matrix with `m >= n`.
+/// Returns `U` (m x n), `σ` and `V` (n x n), column-major.
+#[allow(clippy::type_complexity)]
+fn golub_kahan<T: Float>(
+    mut a: Vec<T>,
+    m: usize,
+    n: usize,
+) -> Result<(Vec<T>, Vec<T>, Vec<T>), LinalgError> {
+    let mut s = vec![T::ZERO; n];
+    let mut e = vec![T::ZERO; n];
+    let mut u = vec![T::ZERO; m * n];
+    let mut v = vec![T::ZERO; n * n];
+    if n == 0 {
+        return Ok((u, s, v));
+    }
+    let mut work = vec![T::ZERO; m];
+    let nct = (m - 1).min(n);
+    let nrt = n.saturating_sub(2).min(m);
+
+    // Householder reflections down to bidiagonal: the diagonal in
+    // `s`, the superdiagonal in `e`.
+    for k in 0..nct.max(nrt) {
+        if k < nct {
+            let col = &mut a[k * m..(k + 1) * m];
+            let mut nrm = col[k..].iter().fold(T::ZERO, |h, &x| h.hypot(x));
+            if nrm != T::ZERO {
+                if col[k] < T::ZERO {
+                    nrm = -nrm;
+                }
+                for x in &mut col[k..] {
+                    *x = *x / nrm;
+                }
+                col[k] = col[k] + T::ONE;
+            }
+            s[k] = -nrm;
+        }
+        for j in k + 1..n {
+            if k < nct && s[k] != T::ZERO {
+                let (head, tail) = a.split_at_mut(j * m);
+                let ck = &head[k * m..(k + 1) * m];
+                let cj = &mut tail[..m];
+                let t = -dot_slice(&ck[k..], &cj[k..]) / ck[k];
+                for i in k..m {
+                    cj[i] = cj[i] + t * ck[i];
+                }
+            }
+            e[j] = a[k + j * m];
+        }
+        if k < nct {
+            u[k * m + k..(k + 1) * m].copy_from_slice(&a[k * m + k..(k + 1) * m]);
+        }
+        if k < nrt {
+            let mut nrm = e[k + 1..].iter().fold(T::ZERO, |h, &x| h.hypot(x));
+            if nrm != T::ZERO {
+                if e[k + 1] < T::ZERO {
+                    nrm = -nrm;
+                }
+                for x in &mut e[k + 1..] {
+                    *x = *x / nrm;
+                }
+                e[k + 1] = e[k + 1] + T::ONE;
+            }
+            e[k] = -nrm;
+            if k + 1 < m && e[k] != T::ZERO {
+                for w in &mut work[k + 1..] {
+                    *w = T::ZERO;
+                }
+                for j in k + 1..n {
+                    for i in k + 1..m {
+                        work[i] = work[i] + e[j] * a[i + j * m];
+                    }
+                }
+                for j in k + 1..n {
+                    let t = -e[j] / e[k + 1];
+                    for i in k + 1..m {
+                        a[i + j * m] = a[i + j * m] + t * work[i];
+                    }
+                }
+            }
+            v[k * n + k + 1..(k + 1) * n].copy_from_slice(&e[k + 1..]);
+        }
+    }
+
+    let mut p = n;
+    if nct < n {
+        s[nct] = a[nct + nct * m];
+    }
+    if nrt + 1 < p {
+        e[nrt] = a[nrt + (p - 1) * m];
+    }
+    e[p - 1] = T::ZERO;
+
+    // Accumulate U.
+    for j in nct..n {
+        u[j * m..(j + 1) * m].fill(T::ZERO);
+        u[j + j * m] = T::ONE;
+    }
+    for k in (0..nct).rev() {
+        if s[k] != T::ZERO {
+            for j in k + 1..n {
+                let (head, tail) = u.split_at_mut(j * m);
+                let ck = &head[k * m..(k + 1) * m];
+                let cj = &mut tail[..m];
+                let t = -dot_slice(&ck[k..], &cj[k..]) / ck[k];
+                for i in k..m {
+                    cj[i] = cj[i] + t * ck[i];
+                }
+            }
+            let col = &mut u[k * m..(k + 1) * m];
+            for x in &mut col[k..] {
+                *x = -*x;
+            }
+            col[k] = T::ONE + col[k];
+            col[..k].fill(T::ZERO);
+        } else {
+            u[k * m..(k + 1) * m].fill(T::ZERO);
+            u[k + k * m] = T::ONE;
+        }
+    }
+
+    // Accumulate V.
+    for k in (0..n).rev() {
+        if k < nrt && e[k] != T::ZERO {
+            for j in k + 1..n {
+                let (head, tail) = v.split_at_mut(j * n);
+                let ck = &head[k * n..(k + 1) * n];
+                let cj = &mut tail[..n];
+                let t = -dot_slice(&ck[k + 1..], &cj[k + 1..]) / ck[k + 1];
+                for i in k + 1..n {
+                    cj[i] = cj[i] + t * ck[i];
+                }
+            }
+        }
+        v[k * n..(k + 1) * n].fill(T::ZERO);
+        v[k + k * n] = T::ONE;
+    }
+
+    // Chase the superdiagonal down to nothing.
+    let eps = T::EPSILON;
+    let tiny = T::from_f64(2.0f64.powi(-966));
+    let two = T::ONE + T::ONE;
+    let mut iter = 0;
+    while p > 0 {
+        if iter > MAX_ITER {
+            return Err(LinalgError::NoConvergence);
+        }
+        // Find the block that's still coupled: e[k] negligible
+        // (or k == -1) marks its top.
+        let mut k = p as isize - 2;
+        while k >= 0 {
+            let ku = k as usize;
+            if e[ku].abs() <= tiny + eps * (s[ku].abs() + s[ku + 1].abs()) {
+                e[ku] = T::ZERO;
+                break;
+            }
+            k -= 1;
+        }
+        let kase;
+        if k == p as isize - 2 {
+            kase = 4;
+        } else {
+            let mut ks = p as isize - 1;
+            while ks > k {
+                let ksu = ks as usize;
+                let t = if ksu != p { e[ksu].abs() } else { T::ZERO }
+                    + if ks != k + 1 {
+                        e[ksu - 1].abs()
+                    } else {
+                        T::ZERO
+                    };
+                if s[ksu].abs() <= tiny + eps * t {
+                    s[ksu] = T::ZERO;
+                    break;
+                }
+                ks -= 1;
+            }
+            if ks == k {
+                kase = 3;
+            } else if ks == p as isize - 1 {
+                kase = 1;
+            } else {
+                kase = 2;
+                k = ks;
+            }
+        }
+        let k = (k + 1) as usize;
+
+        match kase {
+            // s[p-1] is negligible: deflate it.
+            1 => {
+                let mut f = e[p - 2];
+                e[p - 2] = T::ZERO;
+                for j in (k..p - 1).rev() {
+                    let t = s[j].hypot(f);
+                    let (cs, sn) = (s[j] / t, f / t);
+                    s[j] = t;
+                    if j != k {
+                        f = -sn * e[j - 1];
+                        e[j - 1] = cs * e[j - 1];
+                    }
+                    rotate(&mut v, n, j, p - 1, cs, sn);
+                }
+            }
+            // s[k-1] is negligible: split there.
+            2 => {
+                let mut f = e[k - 1];
+                e[k - 1] = T::ZERO;
+                for j in k..p {
+                    let t = s[j].hypot(f);
+                    let (cs, sn) = (s[j] / t, f / t);
+                    s[j] = t;
+                    f = -sn * e[j];
+                    e[j] = cs * e[j];
+                    rotate(&mut u, m, j, k - 1, cs, sn);
+                }
+            }
+            // One implicit QR step with a Wilkinson shift.
+            3 => {
+                let scale = [s[p - 1], s[p - 2], e[p - 2], s[k], e[k]]
+                    .iter()
+                    .fold(T::ZERO, |mx, x| if x.abs() > mx { x.abs() } else { mx });
+                let sp = s[p - 1] / scale;
+                let spm1 = s[p - 2] / scale;
+                let epm1 = e[p - 2] / scale;
+                let sk = s[k] / scale;
+                let ek = e[k] / scale;
+                let b = ((spm1 + sp) * (spm1 - sp) + epm1 * epm1) / two;
+                let c = (sp * epm1) * (sp * epm1);
+                let mut shift = T::ZERO;
+                if b != T::ZERO || c != T::ZERO {
+                    shift = (b * b + c).sqrt();
+                    if b < T::ZERO {
+                        shift = -shift;
+                    }
+                    shift = c / (b + shift);
+                }
+                let mut f = (sk + sp) * (sk - sp) + shift;
+                let mut g = sk * ek;
+                for j in k..p - 1 {
+                    let t = f.hypot(g);
+                    let (cs, sn) = (f / t, g / t);
+                    if j != k {
+                        e[j - 1] = t;
+                    }
+                    f = cs * s[j] + sn * e[j];
+                    e[j] = cs * e[j] - sn * s[j];
+                    g = sn * s[j + 1];
+                    s[j + 1] = cs * s[j + 1];
+                    rotate(&mut v, n, j, j + 1, cs, sn);
+
+                    let t = f.hypot(g);
+                    let (cs, sn) = (f / t, g / t);
+                    s[j] = t;
+                    f = cs * e[j] + sn * s[j + 1];
+                    s[j + 1] = -sn * e[j] + cs * s[j + 1];
+                    g = sn * e[j + 1];
+                    e[j + 1] = cs * e[j + 1];
+                    if j < m - 1 {
+                        rotate(&mut u, m, j, j + 1, cs, sn);
+                    }
+                }
+                e[p - 2] = f;
+                iter += 1;
+            }
+            // s[k] has converged: make it positive and sort it in.
+            _ => {
+                if s[k] <= T::ZERO {
+                    s[k] = if s[k] < T::ZERO { -s[k] } else { T::ZERO };
+                    for x in &mut v[k * n..(k + 1) * n] {
+                        *x = -*x;
+                    }
+                }
+                let mut k = k;
+                while k + 1 < n && s[k] < s[k + 1] {
+                    s.swap(k, k + 1);
+                    for i in 0..n {
+                        v.swap(i + k * n, i + (k + 1) * n);
+                    }
+                    for i in 0..m {
+                        u.swap(i + k * m, i + (k + 1) * m);
+                    }
+                    k += 1;
+                }
+                iter = 0;
+                p -= 1;
+            }
+        }
+    }
+    Ok((u, s, v))
+}
+
+/// # rank()
+/// Takes a `rows` x `cols` row-major matrix.
+///
+/// Returns its numerical rank: how many singular
+/// values are above `Svd::tolerance()`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::rank;
+///
+/// let a = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+/// assert_eq!(Ok(2), rank(&a, 3, 3));
+/// ```
+pub fn rank<T: Float>(a: &[T], rows: usize, cols: usize) -> Result<usize, LinalgError> {
+    Ok(Svd::new(a, rows, cols)?.rank())
+}
+
+/// # condition_number()
+/// Takes a `rows` x `cols` row-major matrix.
+///
+/// Returns its 2-norm condition number, the largest
+/// singular value over the smallest. Infinite if the
+/// matrix is singular.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::condition_number;
+///
+/// let a = [1.0f64, 2.0, 3.0, 4.0];
+/// let c = condition_number(&a, 2, 2).unwrap();
+/// assert!((c - 14.933034373659268).abs() < 1e-9);
+/// ```
+pub fn condition_number<T: Float>(a: &[T], rows: usize, cols: usize) -> Result<T, LinalgError> {
+    Ok(Svd::new(a, rows, cols)?.condition_number())
+}
+
+/// # pseudo_inverse()
+/// Takes a `rows` x `cols` row-major matrix.
+///
+/// Returns its Moore–Penrose pseudo-inverse, `cols`
+/// x `rows` row-major. Works for any shape and rank;
+/// for an invertible matrix it's just the inverse.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::linalg::pseudo_inverse;
+///
+/// let a = [1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0];
+/// let p = pseudo_inverse(&a, 3, 2).unwrap();
+/// let e = [-4.0 / 3.0, -1.0 / 3.0, 2.0 / 3.0, 13.0 / 12.0, 1.0 / 3.0, -5.0 / 12.0];
+/// assert!(p.iter().zip(e).all(|(p, e)| (p - e).abs() < 1e-12));
+/// ```
+pub fn pseudo_inverse<T: Float>(a: &[T], rows: usize, cols: usize) -> Result<Vec<T>, LinalgError> {
+    Ok(Svd::new(a, rows, cols)?.pseudo_inverse())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::{matmul, MatrixView};
+    use crate::test_util::close;
+
+    /// `U·diag(σ)·Vᵀ`, row-major.
+    fn rebuild(svd: &Svd<f64>, rows: usize, cols: usize) -> Vec<f64> {
+        let k = svd.singular_values().len();
+        let mut us = svd.u().to_vec();
+        for (i, x) in us.iter_mut().enumerate() {
+            *x *= svd.singular_values()[i % k];
+        }
+        let us = MatrixView::row_major(&us, rows, k).unwrap();
+        let v = MatrixView::row_major(svd.v(), cols, k).unwrap();
+        matmul(&us, &v.transpose()).unwrap()
+    }
+
+    fn orthonormal(q: &[f64], rows: usize, k: usize) -> bool {
+        let q = MatrixView::row_major(q, rows, k).unwrap();
+        let qtq = matmul(&q.transpose(), &q).unwrap();
+        let id: Vec<f64> = (0..k * k)
+            .map(|x| (x % (k + 1) == 0) as u8 as f64)
+            .collect();
+        close(&id, &qtq, 1e-12)
+    }
+
+    #[test]
+    fn reference_check() {
+        let a = [3.0, 2.0, 2.0, 2.0, 3.0, -2.0];
+        let svd = Svd::new(&a, 2, 3).unwrap();
+        assert!(close(&[5.0, 3.0], svd.singular_values(), 1e-12));
+        assert!(close(&a, &rebuild(&svd, 2, 3), 1e-12));
+
+        // [[4, 0], [3, -5]]: σ = √40, √10.
+        let b = [4.0, 0.0, 3.0, -5.0];
+        let svd = Svd::new(&b, 2, 2).unwrap();
+        let e = [40.0f64.sqrt(), 10.0f64.sqrt()];
+        assert!(close(&e, svd.singular_values(), 1e-12));
+        assert!((svd.condition_number() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn shapes_check() {
+        let tall: Vec<f64> = (0..20).map(|k| ((k * 7) % 11) as f64 - 3.0).collect();
+        for &(r, c) in &[(5, 4), (4, 5), (10, 2), (2, 10), (1, 20), (20, 1)] {
+            let svd = Svd::new(&tall, r, c).unwrap();
+            let k = r.min(c);
+            assert_eq!(k, svd.singular_values().len());
+            assert!(svd.singular_values().windows(2).all(|w| w[0] >= w[1]));
+            assert!(orthonormal(svd.u(), r, k));
+            assert!(orthonormal(svd.v(), c, k));
+            assert!(close(&tall, &rebuild(&svd, r, c), 1e-12));
+        }
+    }
+
+    #[test]
+    fn rank_check() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+        let svd = Svd::new(&a, 3, 3).unwrap();
+        assert_eq!(2, svd.rank());
+        assert!(close(&a, &rebuild(&svd, 3, 3), 1e-12));
+        assert_eq!(Ok(0), rank(&[0.0; 6], 2, 3));
+        assert_eq!(Ok(1), rank(&[1.0, 2.0, 2.0, 4.0], 2, 2));
+        assert_eq!(f64::INFINITY, condition_number(&[0.0; 4], 2, 2).unwrap());
+        assert!(Svd::<f64>::new(&[], 0, 3)
+            .unwrap()
+            .singular_values()
+            .is_empty());
+        assert!(Svd::new(&[1.0, 2.0, 3.0], 2, 2).is_err());
+    }
+
+    #[test]
+    fn pseudo_inverse_check() {
+        // Invertible: the pseudo-inverse is the inverse.
+        let a = [4.0, 7.0, 2.0, 6.0];
+        let p = pseudo_inverse(&a, 2, 2).unwrap();
+        assert!(close(&[0.6, -0.7, -0.2, 0.4], &p, 1e-12));
+
+        // Rank deficient: A·A⁺·A = A.
+        let s = [1.0, 2.0, 3.0, 2.0, 4.0, 6.0];
+        let p = pseudo_inverse(&s, 2, 3).unwrap();
+        let sv = MatrixView::row_major(&s, 2, 3).unwrap();
+        let pv = MatrixView::row_major(&p, 3, 2).unwrap();
+        let sp = matmul(&sv, &pv).unwrap();
+        let spv = MatrixView::row_major(&sp, 2, 2).unwrap();
+        assert!(close(&s, &matmul(&spv, &sv).unwrap(), 1e-12));
+        let e = [
+            1.0 / 70.0,
+            2.0 / 70.0,
+            2.0 / 70.0,
+            4.0 / 70.0,
+            3.0 / 70.0,
+            6.0 / 70.0,
+        ];
+        assert!(close(&e, &p, 1e-12));
+    }
+
+    #[test]
+    fn f32_check() {
+        let svd = Svd::new(&[3.0f32, 0.0, 0.0, -2.0], 2, 2).unwrap();
+        assert_eq!(&[3.0, 2.0], svd.singular_values());
+        assert_eq!(2, svd.rank());
+    }
+}
@@ -0,0 +1,727 @@
+//! Matrices over plain flat slices.
+//!
+//! `MatrixView` and `MatrixViewMut` borrow a slice and say how to read
+//! it as rows and columns: a row stride and a column stride. Row-major
+//! data has strides `(cols, 1)`, column-major `(1, rows)`, and a
+//! transpose is just the strides swapped, no copying.
+//!
+//! Products come back as row-major Vecs and check their shapes first,
+//! handing back a `ShapeError` rather than panicking or truncating.
+//!
+//! `Matrix` is the owned version, with the arithmetic operators.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::dot_slice;
+use crate::num::Num;
+use crate::strided::StridedSlice;
+
+mod owned;
+
+pub use owned::Matrix;
+
+/// Square tile size for the blocked matmul; three 64x64 f64 tiles sit
+/// comfortably in L2.
+const BLOCK: usize = 64;
+
+/// # ShapeError
+/// Error for matrices (and vectors) that don't
+/// fit together, or a buffer that's the wrong size
+/// for the shape it's meant to hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShapeError {
+    /// The buffer doesn't fit `rows` x `cols`: too short for the strides,
+    /// or not exactly `rows * cols` for a plain row/column-major layout.
+    BufferSize { needed: usize, got: usize },
+    /// Two operands' shapes don't line up, as `(rows, cols)`.
+    Mismatch {
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapeError::BufferSize { needed, got } => {
+                write!(
+                    f,
+                    "buffer is the wrong size: needed {needed} elements, got {got}"
+                )
+            }
+            ShapeError::Mismatch { left, right } => write!(
+                f,
+                "shapes don't line up: {}x{} and {}x{}",
+                left.0, left.1, right.0, right.1
+            ),
+        }
+    }
+}
+
+impl Error for ShapeError {}
+
+/// How many elements a strided layout reaches into its buffer.
+fn needed(rows: usize, cols: usize, rs: usize, cs: usize) -> Option<usize> {
+    if rows == 0 || cols == 0 {
+        return Some(0);
+    }
+    (rows - 1)
+        .checked_mul(rs)?
+        .checked_add((cols - 1).checked_mul(cs)?)?
+        .checked_add(1)
+}
+
+fn check(len: usize, rows: usize, cols: usize, rs: usize, cs: usize) -> Result<(), ShapeError> {
+    match needed(rows, cols, rs, cs) {
+        Some(n) if n <= len => Ok(()),
+        Some(n) => Err(ShapeError::BufferSize {
+            needed: n,
+            got: len,
+        }),
+        None => Err(ShapeError::BufferSize {
+            needed: usize::MAX,
+            got: len,
+        }),
+    }
+}
+
+/// Row-major and column-major buffers have to be exactly the right size;
+/// a buffer one short (or one long) is almost always a bug.
+fn check_exact(len: usize, rows: usize, cols: usize) -> Result<(), ShapeError> {
+    match rows.checked_mul(cols) {
+        Some(n) if n == len => Ok(()),
+        n => Err(ShapeError::BufferSize {
+            needed: n.unwrap_or(usize::MAX),
+            got: len,
+        }),
+    }
+}
+
+/// # MatrixView
+/// A borrowed, read-only matrix over a flat slice.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::matrix::MatrixView;
+///
+/// let data = [1, 2, 3, 4, 5, 6];
+/// let m = MatrixView::row_major(&data, 2, 3).unwrap();
+/// assert_eq!(6, m[(1, 2)]);
+/// let t = m.transpose();
+/// assert_eq!((3, 2), t.shape());
+/// assert_eq!(6, t[(2, 1)]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MatrixView<'a, T> {
+    data: &'a [T],
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+impl<'a, T> MatrixView<'a, T> {
+    /// `data` holds the rows one after another, and
+    /// must be exactly `rows * cols` long.
+    pub fn row_major(data: &'a [T], rows: usize, cols: usize) -> Result<Self, ShapeError> {
+        check_exact(data.len(), rows, cols)?;
+        Ok(Self::raw(data, rows, cols, cols, 1))
+    }
+
+    /// `data` holds the columns one after another, and
+    /// must be exactly `rows * cols` long.
+    pub fn col_major(data: &'a [T], rows: usize, cols: usize) -> Result<Self, ShapeError> {
+        check_exact(data.len(), rows, cols)?;
+        Ok(Self::raw(data, rows, cols, 1, rows))
+    }
+
+    /// Element `(i, j)` lives at `data[i * row_stride + j * col_stride]`.
+    /// `data` only has to be long enough to reach the last one.
+    pub fn with_strides(
+        data: &'a [T],
+        rows: usize,
+        cols: usize,
+        row_stride: usize,
+        col_stride: usize,
+    ) -> Result<Self, ShapeError> {
+        check(data.len(), rows, cols, row_stride, col_stride)?;
+        Ok(Self::raw(data, rows, cols, row_stride, col_stride))
+    }
+
+    fn raw(data: &'a [T], rows: usize, cols: usize, rs: usize, cs: usize) -> Self {
+        MatrixView {
+            data,
+            rows,
+            cols,
+            row_stride: rs,
+            col_stride: cs,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// `(row_stride, col_stride)`.
+    pub fn strides(&self) -> (usize, usize) {
+        (self.row_stride, self.col_stride)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<&'a T> {
+        if i < self.rows && j < self.cols {
+            Some(&self.data[i * self.row_stride + j * self.col_stride])
+        } else {
+            None
+        }
+    }
+
+    /// Same matrix flipped over its diagonal. No copying,
+    /// just the strides swapped.
+    pub fn transpose(&self) -> MatrixView<'a, T> {
+        Self::raw(
+            self.data,
+            self.cols,
+            self.rows,
+            self.col_stride,
+            self.row_stride,
+        )
+    }
+
+    /// Row `i` as a plain slice, if its elements sit
+    /// next to each other in the buffer.
+    pub fn row_slice(&self, i: usize) -> Option<&'a [T]> {
+        if i >= self.rows || (self.col_stride != 1 && self.cols > 1) {
+            return None;
+        }
+        let start = i * self.row_stride;
+        Some(&self.data[start..start + self.cols])
+    }
+
+    /// Row `i`, one element at a time.
+    ///
+    /// Panics if `i` is out of range.
+    pub fn row(&self, i: usize) -> impl Iterator<Item = &'a T> + 'a {
+        assert!(i < self.rows, "row {i} out of range for {} rows", self.rows);
+        let (data, start, cs) = (self.data, i * self.row_stride, self.col_stride);
+        (0..self.cols).map(move |j| &data[start + j * cs])
+    }
+
+    /// Column `j`, one element at a time.
+    ///
+    /// Panics if `j` is out of range.
+    pub fn col(&self, j: usize) -> impl Iterator<Item = &'a T> + 'a {
+        assert!(
+            j < self.cols,
+            "column {j} out of range for {} cols",
+            self.cols
+        );
+        let (data, start, rs) = (self.data, j * self.col_stride, self.row_stride);
+        (0..self.rows).map(move |i| &data[start + i * rs])
+    }
+
+    /// Row `i` as a `StridedSlice`, ready for `dot_slice()`.
+    ///
+    /// Panics if `i` is out of range.
+    pub fn row_strided(&self, i: usize) -> StridedSlice<'a, T> {
+        assert!(i < self.rows, "row {i} out of range for {} rows", self.rows);
+        // An empty row can start past the end of the buffer.
+        let data = self.data.get(i * self.row_stride..).unwrap_or(&[]);
+        StridedSlice::new(data, self.cols, self.col_stride as isize)
+            .expect("view was checked on construction")
+    }
+
+    /// Column `j` as a `StridedSlice`, ready for `dot_slice()`.
+    ///
+    /// Panics if `j` is out of range.
+    pub fn col_strided(&self, j: usize) -> StridedSlice<'a, T> {
+        assert!(
+            j < self.cols,
+            "column {j} out of range for {} cols",
+            self.cols
+        );
+        // Same for an empty column.
+        let data = self.data.get(j * self.col_stride..).unwrap_or(&[]);
+        StridedSlice::new(data, self.rows, self.row_stride as isize)
+            .expect("view was checked on construction")
+    }
+}
+
+impl<T: Copy> MatrixView<'_, T> {
+    /// Copies the matrix out into a fresh row-major Vec.
+    pub fn to_vec(&self) -> Vec<T> {
+        (0..self.rows).flat_map(|i| self.row(i).copied()).collect()
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixView<'_, T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        self.get(i, j).unwrap_or_else(|| {
+            panic!(
+                "index ({i}, {j}) out of range for a {}x{} matrix",
+                self.rows, self.cols
+            )
+        })
+    }
+}
+
+/// # MatrixViewMut
+/// A borrowed, writable matrix over a flat slice.
+/// Same layouts as `MatrixView`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::matrix::MatrixViewMut;
+///
+/// let mut data = [0; 6];
+/// let mut m = MatrixViewMut::col_major(&mut data, 2, 3).unwrap();
+/// m[(1, 0)] = 7;
+/// m[(0, 2)] = 9;
+/// assert_eq!([0, 7, 0, 0, 9, 0], data);
+/// ```
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T> {
+    data: &'a mut [T],
+    rows: usize,
+    cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+impl<'a, T> MatrixViewMut<'a, T> {
+    /// See `MatrixView::row_major()`.
+    pub fn row_major(data: &'a mut [T], rows: usize, cols: usize) -> Result<Self, ShapeError> {
+        check_exact(data.len(), rows, cols)?;
+        Ok(Self::raw(data, rows, cols, cols, 1))
+    }
+
+    /// See `MatrixView::col_major()`.
+    pub fn col_major(data: &'a mut [T], rows: usize, cols: usize) -> Result<Self, ShapeError> {
+        check_exact(data.len(), rows, cols)?;
+        Ok(Self::raw(data, rows, cols, 1, rows))
+    }
+
+    /// See `MatrixView::with_strides()`.
+    pub fn with_strides(
+        data: &'a mut [T],
+        rows: usize,
+        cols: usize,
+        row_stride: usize,
+        col_stride: usize,
+    ) -> Result<Self, ShapeError> {
+        check(data.len(), rows, cols, row_stride, col_stride)?;
+        Ok(Self::raw(data, rows, cols, row_stride, col_stride))
+    }
+
+    fn raw(data: &'a mut [T], rows: usize, cols: usize, rs: usize, cs: usize) -> Self {
+        MatrixViewMut {
+            data,
+            rows,
+            cols,
+            row_stride: rs,
+            col_stride: cs,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// A read-only view of the same matrix.
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView::raw(
+            self.data,
+            self.rows,
+            self.cols,
+            self.row_stride,
+            self.col_stride,
+        )
+    }
+
+    /// Same matrix flipped over its diagonal, still writable.
+    pub fn transpose(self) -> MatrixViewMut<'a, T> {
+        Self::raw(
+            self.data,
+            self.cols,
+            self.rows,
+            self.col_stride,
+            self.row_stride,
+        )
+    }
+
+    pub fn get_mut(&mut self, i: usize, j: usize) -> Option<&mut T> {
+        if i < self.rows && j < self.cols {
+            Some(&mut self.data[i * self.row_stride + j * self.col_stride])
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Copy> MatrixViewMut<'_, T> {
+    /// Sets every element to `v`.
+    pub fn fill(&mut self, v: T) {
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                self[(i, j)] = v;
+            }
+        }
+    }
+
+    /// Copies `src` in, element by element. Shapes have to match.
+    pub fn copy_from(&mut self, src: &MatrixView<'_, T>) -> Result<(), ShapeError> {
+        if self.shape() != src.shape() {
+            return Err(ShapeError::Mismatch {
+                left: self.shape(),
+                right: src.shape(),
+            });
+        }
+        for i in 0..self.rows {
+            for (j, &v) in src.row(i).enumerate() {
+                self[(i, j)] = v;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatrixViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(
+            i < self.rows && j < self.cols,
+            "index ({i}, {j}) out of range for a {}x{} matrix",
+            self.rows,
+            self.cols
+        );
+        &self.data[i * self.row_stride + j * self.col_stride]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for MatrixViewMut<'_, T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(
+            i < self.rows && j < self.cols,
+            "index ({i}, {j}) out of range for a {}x{} matrix",
+            self.rows,
+            self.cols
+        );
+        &mut self.data[i * self.row_stride + j * self.col_stride]
+    }
+}
+
+/// # matvec()
+/// Takes a matrix view and a vector.
+///
+/// Returns `m · x` as a new shiny Vec\<T\>, one
+/// `dot_slice()` per row. `x` has to be exactly
+/// `m.cols()` long.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::matrix::{matvec, MatrixView};
+///
+/// let m = MatrixView::row_major(&[1, 2, 3, 4, 5, 6], 2, 3).unwrap();
+/// assert_eq!(Ok(vec![14, 32]), matvec(&m, &[1, 2, 3]));
+/// assert!(matvec(&m, &[1, 2]).is_err());
+/// ```
+pub fn matvec<T: Num>(m: &MatrixView<'_, T>, x: &[T]) -> Result<Vec<T>, ShapeError> {
+    if x.len() != m.cols {
+        return Err(ShapeError::Mismatch {
+            left: m.shape(),
+            right: (x.len(), 1),
+        });
+    }
+    Ok((0..m.rows)
+        .map(|i| match m.row_slice(i) {
+            Some(r) => dot_slice(r, x),
+            None => m.row(i).zip(x).fold(T::ZERO, |acc, (&a, &b)| acc + a * b),
+        })
+        .collect())
+}
+
+/// # vecmat()
+/// Takes a vector and a matrix view.
+///
+/// Returns `x · m` (a row vector times the
+/// matrix) as a new shiny Vec\<T\>. `x` has to be
+/// exactly `m.rows()` long.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::matrix::{vecmat, MatrixView};
+///
+/// let m = MatrixView::row_major(&[1, 2, 3, 4, 5, 6], 2, 3).unwrap();
+/// assert_eq!(Ok(vec![9, 12, 15]), vecmat(&[1, 2], &m));
+/// ```
+pub fn vecmat<T: Num>(x: &[T], m: &MatrixView<'_, T>) -> Result<Vec<T>, ShapeError> {
+    if x.len() != m.rows {
+        return Err(ShapeError::Mismatch {
+            left: (1, x.len()),
+            right: m.shape(),
+        });
+    }
+    matvec(&m.transpose(), x)
+}
+
+/// # matmul()
+/// Takes a couple of matrix views.
+///
+/// Returns `a · b` as a new shiny row-major
+/// Vec\<T\> of `a.rows() * b.cols()` elements.
+/// `a.cols()` has to equal `b.rows()`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::matrix::{matmul, MatrixView};
+///
+/// let a = MatrixView::row_major(&[1, 2, 3, 4], 2, 2).unwrap();
+/// let b = MatrixView::row_major(&[5, 6, 7, 8], 2, 2).unwrap();
+/// assert_eq!(Ok(vec![19, 22, 43, 50]), matmul(&a, &b));
+/// ```
+pub fn matmul<T: Num>(a: &MatrixView<'_, T>, b: &MatrixView<'_, T>) -> Result<Vec<T>, ShapeError> {
+    let mut out = vec![T::ZERO; a.rows * b.cols];
+    let mut c = MatrixViewMut::row_major(&mut out, a.rows, b.cols)?;
+    matmul_into(a, b, &mut c)?;
+    Ok(out)
+}
+
+/// # matmul_into()
+/// Same as `matmul()` but writes into `out`, which
+/// has to be `a.rows()` x `b.cols()`. Whatever was in
+/// `out` gets overwritten.
+///
+/// When the rows of `a` and the columns of `b`
+/// are contiguous (say `b` is column-major) it's one
+/// `dot_slice()` per element. Otherwise it works
+/// through the matrices in square tiles so the bits
+/// being worked on stay in cache.
+pub fn matmul_into<T: Num>(
+    a: &MatrixView<'_, T>,
+    b: &MatrixView<'_, T>,
+    out: &mut MatrixViewMut<'_, T>,
+) -> Result<(), ShapeError> {
+    if a.cols != b.rows {
+        return Err(ShapeError::Mismatch {
+            left: a.shape(),
+            right: b.shape(),
+        });
+    }
+    if out.shape() != (a.rows, b.cols) {
+        return Err(ShapeError::Mismatch {
+            left: (a.rows, b.cols),
+            right: out.shape(),
+        });
+    }
+    out.fill(T::ZERO);
+    let (m, k, n) = (a.rows, a.cols, b.cols);
+    if k == 0 {
+        return Ok(());
+    }
+    let (ars, acs) = (a.row_stride, a.col_stride);
+    let (brs, bcs) = (b.row_stride, b.col_stride);
+    let (ors, ocs) = (out.row_stride, out.col_stride);
+
+    // Rows of `a` and columns of `b` both contiguous: every element
+    // is one `dot_slice()`.
+    if k == 1 || (acs == 1 && brs == 1) {
+        for i in 0..m {
+            let row = &a.data[i * ars..][..k];
+            for j in 0..n {
+                let o = i * ors + j * ocs;
+                out.data[o] = dot_slice(row, &b.data[j * bcs..][..k]);
+            }
+        }
+        return Ok(());
+    }
+
+    // Otherwise square tiles, so the bits being worked on stay in
+    // cache. With contiguous rows in `b` and `out` the inner loop is
+    // over a pair of row slices.
+    let rows = (bcs == 1 && ocs == 1) || n == 1;
+    for i0 in (0..m).step_by(BLOCK) {
+        for p0 in (0..k).step_by(BLOCK) {
+            for j0 in (0..n).step_by(BLOCK) {
+                let j1 = (j0 + BLOCK).min(n);
+                for i in i0..(i0 + BLOCK).min(m) {
+                    for p in p0..(p0 + BLOCK).min(k) {
+                        let aip = a.data[i * ars + p * acs];
+                        if rows {
+                            let b_row = &b.data[p * brs + j0..p * brs + j1];
+                            let o_row = &mut out.data[i * ors + j0..i * ors + j1];
+                            for (o, &x) in o_row.iter_mut().zip(b_row) {
+                                *o = *o + aip * x;
+                            }
+                        } else {
+                            for j in j0..j1 {
+                                let o = i * ors + j * ocs;
+                                out.data[o] = out.data[o] + aip * b.data[p * brs + j * bcs];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive(a: &MatrixView<'_, i64>, b: &MatrixView<'_, i64>) -> Vec<i64> {
+        let mut out = Vec::new();
+        for i in 0..a.rows() {
+            for j in 0..b.cols() {
+                out.push((0..a.cols()).map(|p| a[(i, p)] * b[(p, j)]).sum());
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn layout_check() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let r = MatrixView::row_major(&data, 2, 3).unwrap();
+        let c = MatrixView::col_major(&data, 3, 2).unwrap();
+        assert_eq!(r.to_vec(), c.transpose().to_vec());
+        assert_eq!(Some(&[4, 5, 6][..]), r.row_slice(1));
+        assert_eq!(None, c.row_slice(0));
+        assert_eq!(vec![&2, &5], r.col(1).collect::<Vec<_>>());
+        assert_eq!(None, r.get(2, 0));
+
+        // Every other element of a longer buffer.
+        let s = MatrixView::with_strides(&data, 2, 2, 3, 2).unwrap();
+        assert_eq!(vec![1, 3, 4, 6], s.to_vec());
+    }
+
+    #[test]
+    fn shape_error_check() {
+        let data = [0; 6];
+        assert_eq!(
+            Err(ShapeError::BufferSize { needed: 8, got: 6 }),
+            MatrixView::row_major(&data, 2, 4).map(|_| ())
+        );
+        assert_eq!(
+            Err(ShapeError::BufferSize { needed: 7, got: 6 }),
+            MatrixView::with_strides(&data, 2, 2, 5, 1).map(|_| ())
+        );
+        assert!(MatrixView::with_strides(&data, 2, 2, usize::MAX, 1).is_err());
+        assert!(MatrixView::<i32>::row_major(&[], 0, 5).is_ok());
+
+        let a = MatrixView::row_major(&data, 2, 3).unwrap();
+        let e = ShapeError::Mismatch {
+            left: (2, 3),
+            right: (2, 3),
+        };
+        assert_eq!(Err(e), matmul(&a, &a));
+        assert_eq!("shapes don't line up: 2x3 and 2x3", e.to_string());
+        assert!(vecmat(&[1, 2, 3], &a).is_err());
+    }
+
+    #[test]
+    fn matmul_check() {
+        // Big enough to cross tile edges.
+        let (m, k, n) = (70, 130, 65);
+        let ad: Vec<i64> = (0..m * k).map(|x| (x as i64 * 37) % 11 - 5).collect();
+        let bd: Vec<i64> = (0..k * n).map(|x| (x as i64 * 53) % 13 - 6).collect();
+        let a = MatrixView::row_major(&ad, m, k).unwrap();
+        let b = MatrixView::col_major(&bd, k, n).unwrap();
+        assert_eq!(naive(&a, &b), matmul(&a, &b).unwrap());
+
+        let bt = b.transpose();
+        let at = a.transpose();
+        let lhs = matmul(&bt, &at).unwrap();
+        let rhs = MatrixView::row_major(&lhs, n, m)
+            .unwrap()
+            .transpose()
+            .to_vec();
+        assert_eq!(naive(&a, &b), rhs);
+
+        // Every mix of layouts, covering the dot, row and plain paths.
+        let a2 = MatrixView::col_major(&ad, m, k).unwrap();
+        let b2 = MatrixView::row_major(&bd, k, n).unwrap();
+        for (a, b) in [(a, b2), (a2, b), (a2, b2)] {
+            let e = naive(&a, &b);
+            assert_eq!(e, matmul(&a, &b).unwrap());
+            let mut cd = vec![0; m * n];
+            let mut c = MatrixViewMut::col_major(&mut cd, m, n).unwrap();
+            matmul_into(&a, &b, &mut c).unwrap();
+            assert_eq!(e, c.as_view().to_vec());
+        }
+        let empty = MatrixView::row_major(&[], 2, 0).unwrap();
+        let b0 = MatrixView::row_major(&[], 0, 3).unwrap();
+        assert_eq!(vec![0; 6], matmul(&empty, &b0).unwrap());
+    }
+
+    #[test]
+    fn strided_check() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let m = MatrixView::row_major(&data, 2, 3).unwrap();
+        assert_eq!(vec![2, 5], m.col_strided(1).to_vec());
+        assert_eq!(vec![4, 5, 6], m.row_strided(1).to_vec());
+        assert_eq!(Some(&data[3..]), m.row_strided(1).as_slice());
+        let t = m.transpose();
+        assert_eq!(vec![4, 5, 6], t.col_strided(1).to_vec());
+
+        let empty = MatrixView::<f64>::row_major(&[], 0, 5).unwrap();
+        assert!(empty.col_strided(2).to_vec().is_empty());
+        assert!(empty.transpose().row_strided(4).to_vec().is_empty());
+        let flat = MatrixView::<f64>::col_major(&[], 3, 0).unwrap();
+        assert!(flat.row_strided(2).to_vec().is_empty());
+        assert_eq!(
+            2 * 3 + 5 * 6,
+            dot_slice(&m.col_strided(1), &m.col_strided(2))
+        );
+    }
+
+    #[test]
+    fn matvec_check() {
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let m = MatrixView::col_major(&data, 2, 3).unwrap();
+        assert_eq!(Ok(vec![22.0, 28.0]), matvec(&m, &[1.0, 2.0, 3.0]));
+        assert_eq!(Ok(vec![5.0, 11.0, 17.0]), vecmat(&[1.0, 2.0], &m));
+
+        let mut out = [0.0; 4];
+        let mut c = MatrixViewMut::row_major(&mut out, 2, 2).unwrap();
+        let t = m.transpose();
+        matmul_into(&m, &t, &mut c).unwrap();
+        assert_eq!([35.0, 44.0, 44.0, 56.0], out);
+    }
+
+    #[test]
+    fn view_mut_check() {
+        let mut data = [0; 6];
+        let mut m = MatrixViewMut::row_major(&mut data, 2, 3).unwrap();
+        let src = [1, 2, 3, 4, 5, 6];
+        m.copy_from(&MatrixView::col_major(&src, 2, 3).unwrap())
+            .unwrap();
+        *m.get_mut(0, 0).unwrap() = 9;
+        let mut t = m.transpose();
+        t[(2, 1)] = 0;
+        assert_eq!([9, 3, 5, 2, 4, 0], data);
+    }
+}
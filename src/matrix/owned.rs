@@ -0,0 +1,391 @@
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+use super::{matmul_into, MatrixView, MatrixViewMut, ShapeError};
+use crate::num::Num;
+use crate::{add_slice, mul_slice, sub_slice};
+
+/// # Matrix
+/// An owned, row-major matrix.
+///
+/// Element-wise arithmetic runs through the same
+/// kernels as `add_slice()` / `mul_slice()`, and the
+/// product through `matmul()`, so results match the
+/// slice functions exactly.
+///
+/// The operators panic when shapes don't line up,
+/// like indexing out of range does; the `try_*`
+/// methods hand back a `ShapeError` instead.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::matrix::Matrix;
+///
+/// let a = Matrix::from_vec(vec![1, 2, 3, 4], 2, 2).unwrap();
+/// let i = Matrix::identity(2);
+/// assert_eq!(a, &a * &i);
+/// assert_eq!(Matrix::from_vec(vec![2, 4, 6, 8], 2, 2).unwrap(), &a + &a);
+/// assert_eq!(4, (a * 2)[(0, 1)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Matrix<T> {
+    /// Wraps a row-major Vec, which has to be exactly
+    /// `rows * cols` long.
+    pub fn from_vec(data: Vec<T>, rows: usize, cols: usize) -> Result<Self, ShapeError> {
+        MatrixView::row_major(&data, rows, cols)?;
+        Ok(Matrix { data, rows, cols })
+    }
+
+    /// Builds a matrix by calling `f(i, j)` for every element.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let data = (0..rows * cols).map(|k| f(k / cols, k % cols)).collect();
+        Matrix { data, rows, cols }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `(rows, cols)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// The row-major buffer.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn view(&self) -> MatrixView<'_, T> {
+        MatrixView::raw(&self.data, self.rows, self.cols, self.cols, 1)
+    }
+
+    pub fn view_mut(&mut self) -> MatrixViewMut<'_, T> {
+        MatrixViewMut::raw(&mut self.data, self.rows, self.cols, self.cols, 1)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        if i < self.rows && j < self.cols {
+            Some(&self.data[i * self.cols + j])
+        } else {
+            None
+        }
+    }
+
+    /// Row `i` as a slice.
+    ///
+    /// Panics if `i` is out of range.
+    pub fn row(&self, i: usize) -> &[T] {
+        assert!(i < self.rows, "row {i} out of range for {} rows", self.rows);
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Column `j`, one element at a time.
+    ///
+    /// Panics if `j` is out of range.
+    pub fn col(&self, j: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            j < self.cols,
+            "column {j} out of range for {} cols",
+            self.cols
+        );
+        self.data.iter().skip(j).step_by(self.cols.max(1))
+    }
+
+    /// Every row, top to bottom, as slices.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.rows).map(move |i| self.row(i))
+    }
+
+    /// Every column, left to right, each one an iterator
+    /// down the column.
+    pub fn iter_cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(move |j| self.col(j))
+    }
+}
+
+impl<T: Copy> Matrix<T> {
+    /// Flipped over its diagonal, into a new matrix.
+    pub fn transpose(&self) -> Matrix<T> {
+        Matrix {
+            data: self.view().transpose().to_vec(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+}
+
+impl<T: Copy> From<MatrixView<'_, T>> for Matrix<T> {
+    fn from(v: MatrixView<'_, T>) -> Self {
+        Matrix {
+            data: v.to_vec(),
+            rows: v.rows(),
+            cols: v.cols(),
+        }
+    }
+}
+
+impl<T: Num> Matrix<T> {
+    /// All zeros.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix {
+            data: vec![T::ZERO; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// `n` x `n`, ones down the diagonal, zeros everywhere else.
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| if i == j { T::ONE } else { T::ZERO })
+    }
+
+    fn same_shape(&self, o: &Matrix<T>) -> Result<(), ShapeError> {
+        if self.shape() != o.shape() {
+            return Err(ShapeError::Mismatch {
+                left: self.shape(),
+                right: o.shape(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Element-wise sum, via `add_slice()`.
+    pub fn try_add(&self, o: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.same_shape(o)?;
+        Ok(self.with_data(add_slice(&self.data, &o.data)))
+    }
+
+    /// Element-wise difference, via `sub_slice()`.
+    pub fn try_sub(&self, o: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.same_shape(o)?;
+        Ok(self.with_data(sub_slice(&self.data, &o.data)))
+    }
+
+    /// Element-wise (Hadamard) product, via `mul_slice()`.
+    pub fn try_hadamard(&self, o: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        self.same_shape(o)?;
+        Ok(self.with_data(mul_slice(&self.data, &o.data)))
+    }
+
+    /// Matrix product, via `matmul()`.
+    pub fn try_mul(&self, o: &Matrix<T>) -> Result<Matrix<T>, ShapeError> {
+        let mut out = Matrix::zeros(self.rows, o.cols);
+        matmul_into(&self.view(), &o.view(), &mut out.view_mut())?;
+        Ok(out)
+    }
+
+    /// Every element times `k`.
+    pub fn scale(&self, k: T) -> Matrix<T> {
+        self.with_data(self.data.iter().map(|&x| x * k).collect())
+    }
+
+    fn with_data(&self, data: Vec<T>) -> Matrix<T> {
+        Matrix {
+            data,
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(
+            i < self.rows && j < self.cols,
+            "index ({i}, {j}) out of range for a {}x{} matrix",
+            self.rows,
+            self.cols
+        );
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(
+            i < self.rows && j < self.cols,
+            "index ({i}, {j}) out of range for a {}x{} matrix",
+            self.rows,
+            self.cols
+        );
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+/// Wires a `try_*` method up to an operator, for every mix of owned
+/// and borrowed operands. Shape mismatches panic.
+macro_rules! matrix_op {
+    ($tr:ident, $f:ident, $try:ident) => {
+        impl<T: Num> $tr<&Matrix<T>> for &Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $f(self, o: &Matrix<T>) -> Matrix<T> {
+                self.$try(o).unwrap_or_else(|e| panic!("{e}"))
+            }
+        }
+
+        impl<T: Num> $tr<Matrix<T>> for &Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $f(self, o: Matrix<T>) -> Matrix<T> {
+                self.$f(&o)
+            }
+        }
+
+        impl<T: Num> $tr<&Matrix<T>> for Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $f(self, o: &Matrix<T>) -> Matrix<T> {
+                (&self).$f(o)
+            }
+        }
+
+        impl<T: Num> $tr<Matrix<T>> for Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $f(self, o: Matrix<T>) -> Matrix<T> {
+                (&self).$f(&o)
+            }
+        }
+    };
+}
+
+matrix_op!(Add, add, try_add);
+matrix_op!(Sub, sub, try_sub);
+matrix_op!(Mul, mul, try_mul);
+
+impl<T: Num> Mul<T> for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, k: T) -> Matrix<T> {
+        self.scale(k)
+    }
+}
+
+impl<T: Num> Mul<T> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, k: T) -> Matrix<T> {
+        self.scale(k)
+    }
+}
+
+/// One bracketed row per line, columns right-aligned.
+/// Width and precision flags get passed on to the elements.
+impl<T: fmt::Display> fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self
+            .data
+            .iter()
+            .map(|x| match f.precision() {
+                Some(p) => format!("{x:.p$}"),
+                None => format!("{x}"),
+            })
+            .collect();
+        let w = cells
+            .iter()
+            .map(|c| c.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(f.width().unwrap_or(0));
+        for i in 0..self.rows {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "[")?;
+            for j in 0..self.cols {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{:>w$}", cells[i * self.cols + j])?;
+            }
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn m(data: &[i32], rows: usize, cols: usize) -> Matrix<i32> {
+        Matrix::from_vec(data.to_vec(), rows, cols).unwrap()
+    }
+
+    #[test]
+    fn constructor_check() {
+        assert!(Matrix::from_vec(vec![1, 2, 3], 2, 2).is_err());
+        assert_eq!(m(&[0, 0, 0, 0, 0, 0], 2, 3), Matrix::zeros(2, 3));
+        assert_eq!(m(&[1, 0, 0, 1], 2, 2), Matrix::identity(2));
+        assert_eq!(
+            m(&[0, 1, 10, 11], 2, 2),
+            Matrix::from_fn(2, 2, |i, j| (i * 10 + j) as i32)
+        );
+        let a = m(&[1, 2, 3, 4, 5, 6], 2, 3);
+        assert_eq!(a, Matrix::from(a.view()));
+        assert_eq!(m(&[1, 4, 2, 5, 3, 6], 3, 2), a.transpose());
+    }
+
+    #[test]
+    fn iterator_check() {
+        let a = m(&[1, 2, 3, 4, 5, 6], 2, 3);
+        assert_eq!(
+            vec![&[1, 2, 3][..], &[4, 5, 6]],
+            a.iter_rows().collect::<Vec<_>>()
+        );
+        let cols: Vec<Vec<i32>> = a.iter_cols().map(|c| c.copied().collect()).collect();
+        assert_eq!(vec![vec![1, 4], vec![2, 5], vec![3, 6]], cols);
+        assert_eq!(None, a.get(0, 3));
+    }
+
+    #[test]
+    fn arithmetic_check() {
+        let a = m(&[1, 2, 3, 4, 5, 6], 2, 3);
+        let b = m(&[6, 5, 4, 3, 2, 1], 2, 3);
+        assert_eq!(m(&[7; 6], 2, 3), &a + &b);
+        assert_eq!(m(&[-5, -3, -1, 1, 3, 5], 2, 3), a.clone() - b.clone());
+        assert_eq!(Ok(m(&[6, 10, 12, 12, 10, 6], 2, 3)), a.try_hadamard(&b));
+        assert_eq!(m(&[28, 10, 73, 28], 2, 2), &a * b.transpose());
+        assert_eq!(m(&[3, 6, 9, 12, 15, 18], 2, 3), &a * 3);
+        assert!(a.try_mul(&b).is_err());
+        assert!(a.try_add(&b.transpose()).is_err());
+
+        let mut c = a.clone();
+        c[(1, 1)] = 0;
+        assert_eq!(0, c.as_slice()[4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "shapes don't line up: 2x3 and 3x2")]
+    fn add_mismatch_panics() {
+        let a = m(&[1, 2, 3, 4, 5, 6], 2, 3);
+        let _ = &a + &a.transpose();
+    }
+
+    #[test]
+    fn display_check() {
+        let a = m(&[1, -20, 300, 4], 2, 2);
+        assert_eq!("[  1, -20]\n[300,   4]", a.to_string());
+        let f = Matrix::from_vec(vec![0.5, 2.0], 1, 2).unwrap();
+        assert_eq!("[0.50, 2.00]", format!("{f:.2}"));
+        assert_eq!("", Matrix::<i32>::zeros(0, 0).to_string());
+    }
+}
@@ -0,0 +1,250 @@
+//! Vector norms.
+//!
+//! An empty slice has a norm of zero. L1 and Linf work on any `Num`
+//! (integers too); the rest need a `Float`. Integer norms saturate
+//! instead of overflowing: `|i8::MIN|` counts as `i8::MAX`, and an L1
+//! sum that won't fit comes back as `MAX`. The L2 and Lp norms scale by the
+//! biggest element as they go (same trick as BLAS `nrm2`), so squaring
+//! huge or tiny values doesn't overflow or underflow.
+
+use crate::num::{Float, Num};
+
+/// Biggest of some magnitudes, zero if there are none, or the first
+/// NaN if there is one.
+pub(crate) fn max_of<T: Num + PartialOrd>(it: impl Iterator<Item = T>) -> T {
+    let mut m = T::ZERO;
+    for x in it {
+        if x.partial_cmp(&m).is_none() {
+            return x;
+        }
+        if x > m {
+            m = x;
+        }
+    }
+    m
+}
+
+/// `Σ (|x| / scale)^p`, with `scale` tracking the biggest |x| seen so
+/// far. Returns `(scale, ssq)`, so the norm is `scale * ssq^(1/p)`.
+pub(crate) fn scaled_sum_pow<T: Float>(it: impl Iterator<Item = T>, p: T) -> (T, T) {
+    let mut scale = T::ZERO;
+    let mut ssq = T::ONE;
+    for x in it {
+        let ax = x.abs();
+        if ax.is_nan() {
+            return (ax, T::ONE);
+        }
+        if ax == T::ZERO {
+            continue;
+        }
+        if scale < ax {
+            ssq = T::ONE + ssq * (scale / ax).powf(p);
+            scale = ax;
+        } else {
+            ssq = ssq + (ax / scale).powf(p);
+        }
+    }
+    (scale, ssq)
+}
+
+/// Same as `scaled_sum_pow()` with p = 2, minus the `powf()` calls.
+pub(crate) fn scaled_sum_sq<T: Float>(it: impl Iterator<Item = T>) -> (T, T) {
+    let mut scale = T::ZERO;
+    let mut ssq = T::ONE;
+    for x in it {
+        let ax = x.abs();
+        if ax.is_nan() {
+            return (ax, T::ONE);
+        }
+        if ax == T::ZERO {
+            continue;
+        }
+        if scale < ax {
+            let r = scale / ax;
+            ssq = T::ONE + ssq * r * r;
+            scale = ax;
+        } else {
+            let r = ax / scale;
+            ssq = ssq + r * r;
+        }
+    }
+    (scale, ssq)
+}
+
+/// # l1_norm()
+/// Takes a reference to a slice of numbers.
+///
+/// Returns the sum of the absolute values
+/// (taxicab norm). Integers saturate at `MAX`
+/// rather than overflow.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::norm::l1_norm;
+///
+/// assert_eq!(6.0, l1_norm(&[1.0, -2.0, 3.0]));
+/// assert_eq!(6, l1_norm(&[1, -2, 3]));
+/// ```
+pub fn l1_norm<T: Num + PartialOrd>(a: &[T]) -> T {
+    a.iter()
+        .fold(T::ZERO, |acc, &x| acc.add_saturating(x.abs_saturating()))
+}
+
+/// # l2_norm()
+/// Takes a reference to a float slice.
+///
+/// Returns its Euclidean length. Scaled as it
+/// goes, so it won't overflow even when the
+/// squares would.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::norm::l2_norm;
+///
+/// assert_eq!(5.0, l2_norm(&[3.0, -4.0]));
+/// assert_eq!(5e300, l2_norm(&[3e300, 4e300]));
+/// ```
+pub fn l2_norm<T: Float>(a: &[T]) -> T {
+    let (scale, ssq) = scaled_sum_sq(a.iter().copied());
+    scale * ssq.sqrt()
+}
+
+/// # linf_norm()
+/// Takes a reference to a slice of numbers.
+///
+/// Returns the biggest absolute value
+/// (max norm). Any NaN makes it NaN. For
+/// signed integers `|MIN|` comes out as `MAX`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::norm::linf_norm;
+///
+/// assert_eq!(3.0, linf_norm(&[1.0, -3.0, 2.0]));
+/// assert_eq!(7u8, linf_norm(&[1, 7, 2]));
+/// assert_eq!(i8::MAX, linf_norm(&[i8::MIN]));
+/// ```
+pub fn linf_norm<T: Num + PartialOrd>(a: &[T]) -> T {
+    max_of(a.iter().map(|&x| x.abs_saturating()))
+}
+
+/// # lp_norm()
+/// Takes a reference to a float slice and a `p`.
+///
+/// Returns `(Σ |x|^p)^(1/p)`, scaled so it won't
+/// overflow. `p` of infinity gives `linf_norm()`.
+/// `p` below 1 isn't a proper norm but you get the
+/// number anyway; `p` of zero or less is NaN.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::norm::lp_norm;
+///
+/// let t = [1.0, -2.0, 2.0];
+/// assert_eq!(5.0, lp_norm(&t, 1.0));
+/// assert_eq!(3.0, lp_norm(&t, 2.0));
+/// assert_eq!(2.0, lp_norm(&t, f64::INFINITY));
+/// ```
+pub fn lp_norm<T: Float>(a: &[T], p: T) -> T {
+    if p.is_nan() || p <= T::ZERO {
+        return T::NAN;
+    }
+    if p == T::INFINITY {
+        return linf_norm(a);
+    }
+    if p == T::ONE {
+        return l1_norm(a);
+    }
+    if p == T::ONE + T::ONE {
+        return l2_norm(a);
+    }
+    let (scale, ssq) = scaled_sum_pow(a.iter().copied(), p);
+    scale * ssq.powf(T::ONE / p)
+}
+
+/// # normalize_slice()
+/// Takes a reference to a float slice.
+///
+/// Returns a new shiny Vec\<T\> pointing the same
+/// way with an L2 norm of one, or `None` if the
+/// slice is all zeros (or empty, or NaN).
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::norm::normalize_slice;
+///
+/// assert_eq!(Some(vec![0.6, -0.8]), normalize_slice(&[3.0, -4.0]));
+/// assert_eq!(None, normalize_slice(&[0.0, 0.0]));
+/// ```
+pub fn normalize_slice<T: Float>(a: &[T]) -> Option<Vec<T>> {
+    let mut v = a.to_vec();
+    normalize_assign_slice(&mut v)?;
+    Some(v)
+}
+
+/// # normalize_assign_slice()
+/// Same as `normalize_slice()` but scales `a`
+/// in place, no allocation.
+///
+/// Returns the L2 norm `a` had before, or `None`
+/// (leaving `a` alone) if it couldn't be normalized.
+pub fn normalize_assign_slice<T: Float>(a: &mut [T]) -> Option<T> {
+    let n = l2_norm(a);
+    if n == T::ZERO || n.is_nan() || n == T::INFINITY {
+        return None;
+    }
+    for x in a.iter_mut() {
+        *x = *x / n;
+    }
+    Some(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_check() {
+        let e: [f32; 0] = [];
+        assert_eq!(0.0, l1_norm(&e));
+        assert_eq!(0.0, l2_norm(&e));
+        assert_eq!(0.0, linf_norm(&e));
+        assert_eq!(0.0, lp_norm(&e, 3.0));
+        assert_eq!(None, normalize_slice(&e));
+        assert_eq!(0u32, l1_norm(&[]));
+        assert_eq!(0i64, linf_norm(&[]));
+    }
+
+    #[test]
+    fn integer_check() {
+        assert_eq!(10, l1_norm(&[-4i32, 0, 6]));
+        assert_eq!(6, linf_norm(&[-4i32, 0, 6]));
+        assert_eq!(-(i8::MIN + 1), linf_norm(&[3, i8::MIN + 1]));
+        assert_eq!(i8::MAX, linf_norm(&[i8::MIN]));
+        assert_eq!(i32::MAX, l1_norm(&[i32::MIN, 1]));
+        assert_eq!(i64::MAX, l1_norm(&[i64::MAX, i64::MAX]));
+        assert_eq!(u8::MAX, l1_norm(&[200u8, 100]));
+        assert_eq!(250u8, l1_norm(&[200u8, 50]));
+        assert!(linf_norm(&[1.0, f64::NAN, 3.0]).is_nan());
+        assert!(l1_norm(&[f32::NAN]).is_nan());
+    }
+
+    #[test]
+    fn scaled_check() {
+        assert_eq!(5e-300, l2_norm(&[3e-300, -4e-300]));
+        assert_eq!(5e30f32, l2_norm(&[3e30f32, 4e30]));
+        let q = lp_norm(&[1e200, 1e200], 3.0);
+        assert!((q / (2f64.cbrt() * 1e200) - 1.0).abs() < 1e-12);
+        assert!(l2_norm(&[1.0, f64::NAN]).is_nan());
+        assert!(lp_norm(&[1.0], 0.0).is_nan());
+    }
+
+    #[test]
+    fn normalize_check() {
+        let mut t = [0.0, 1e-200, 0.0];
+        assert_eq!(Some(1e-200), normalize_assign_slice(&mut t));
+        assert_eq!([0.0, 1.0, 0.0], t);
+        let v = normalize_slice(&[1.0f32, 1.0, 1.0, 1.0]).unwrap();
+        assert_eq!(vec![0.5; 4], v);
+    }
+}
@@ -0,0 +1,336 @@
+//! Number traits shared across slicenator.
+//!
+//! Instead of spelling out `Mul + Mul<Output = T> + Copy + Clone + ...`
+//! on every function, the crate leans on a small family:
+//!
+//! - `Zero` and `One`: the two constants everything needs.
+//! - `Num`: copyable, comparable for equality, and closed under
+//!   `+ - * /`. All the primitive numbers, plus `Wrapping` integers.
+//! - `Integer`: `Num` with a total order, `%` and the bit ops.
+//! - `Field`: `Num` with negation, so division really is the inverse
+//!   of multiplication (give or take rounding).
+//! - `Float`: a `Field` with the usual float toolbox, f32 and f64.
+//!
+//! `complex::Complex` is a `Num` (and a `Field` over a `Field`) too.
+
+use std::iter::Sum;
+
+use crate::simd::{self, DotKernel, MulKernel};
+use std::num::Wrapping;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
+
+/// # Zero
+/// Has an additive identity.
+pub trait Zero: Sized {
+    const ZERO: Self;
+}
+
+/// # One
+/// Has a multiplicative identity.
+pub trait One: Sized {
+    const ONE: Self;
+}
+
+/// # Num
+/// Anything slicenator can do arithmetic on.
+///
+/// Implemented for every primitive integer and
+/// float, and for `Wrapping` integers. Implement
+/// it for your own types to use them everywhere
+/// `Num` shows up.
+pub trait Num:
+    Copy
+    + PartialEq
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// SIMD dot product kernel for `Self`, if the
+    /// crate has one. Leave it alone.
+    #[doc(hidden)]
+    fn dot_kernel() -> Option<DotKernel<Self>> {
+        None
+    }
+
+    /// SIMD element-wise multiply kernel for `Self`,
+    /// if the crate has one. Leave it alone.
+    #[doc(hidden)]
+    fn mul_kernel() -> Option<MulKernel<Self>> {
+        None
+    }
+
+    /// `|self|` for the norms. The primitive integers
+    /// saturate (`|MIN|` is `MAX`), floats are plain
+    /// `abs()`. Leave it alone.
+    #[doc(hidden)]
+    fn abs_saturating(self) -> Self
+    where
+        Self: PartialOrd,
+    {
+        if self < Self::ZERO {
+            Self::ZERO - self
+        } else {
+            self
+        }
+    }
+
+    /// `self + o` for the norms, saturating at the
+    /// ends for the primitive integers. Leave it alone.
+    #[doc(hidden)]
+    fn add_saturating(self, o: Self) -> Self {
+        self + o
+    }
+}
+
+/// # Integer
+/// The primitive integers: totally ordered,
+/// with `%`, shifts and the bit ops.
+pub trait Integer:
+    Num
+    + Eq
+    + Ord
+    + Rem<Output = Self>
+    + Not<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    const MIN: Self;
+    const MAX: Self;
+    const BITS: u32;
+
+    fn checked_add(self, o: Self) -> Option<Self>;
+    fn checked_sub(self, o: Self) -> Option<Self>;
+    fn checked_mul(self, o: Self) -> Option<Self>;
+    fn checked_div(self, o: Self) -> Option<Self>;
+    fn checked_rem(self, o: Self) -> Option<Self>;
+    fn wrapping_add(self, o: Self) -> Self;
+    fn wrapping_sub(self, o: Self) -> Self;
+    fn wrapping_mul(self, o: Self) -> Self;
+    fn wrapping_div(self, o: Self) -> Self;
+    fn wrapping_rem(self, o: Self) -> Self;
+    fn saturating_add(self, o: Self) -> Self;
+    fn saturating_sub(self, o: Self) -> Self;
+    fn saturating_mul(self, o: Self) -> Self;
+    fn saturating_div(self, o: Self) -> Self;
+}
+
+/// # Field
+/// A `Num` you can negate, where dividing undoes
+/// multiplying. The floats, for a start.
+pub trait Field: Num + Neg<Output = Self> {}
+
+/// # Float
+/// The floating point types, f32 and f64.
+///
+/// Just enough to write the numerically careful
+/// stuff once instead of twice.
+pub trait Float: Field + PartialOrd + for<'a> Sum<&'a Self> {
+    const NAN: Self;
+    const INFINITY: Self;
+    /// Gap between 1 and the next float up.
+    const EPSILON: Self;
+
+    /// `self * a + b` with a single rounding.
+    fn mul_add(self, a: Self, b: Self) -> Self;
+    fn abs(self) -> Self;
+    fn is_nan(self) -> bool;
+    fn sqrt(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    /// `sqrt(self² + o²)` without overflowing on the way.
+    fn hypot(self, o: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    /// Angle of the point `(x, self)`, in `(-π, π]`.
+    fn atan2(self, x: Self) -> Self;
+    fn to_f64(self) -> f64;
+    fn from_f64(v: f64) -> Self;
+}
+
+/// Hands `Integer` methods straight to the inherent ones.
+macro_rules! forward {
+    ($($f:ident),* => $ret:ty) => {$(
+        fn $f(self, o: Self) -> $ret {
+            Self::$f(self, o)
+        }
+    )*};
+}
+
+macro_rules! impl_integer {
+    ($($t:ty $(=> $dot:path)?),*) => {$(
+        impl Zero for $t {
+            const ZERO: Self = 0;
+        }
+
+        impl One for $t {
+            const ONE: Self = 1;
+        }
+
+        impl Num for $t {
+            $(
+                fn dot_kernel() -> Option<DotKernel<Self>> {
+                    if simd::INT_LANES {
+                        Some($dot)
+                    } else {
+                        None
+                    }
+                }
+            )?
+
+            fn abs_saturating(self) -> Self {
+                // Unsigned types have `checked_neg()` too; it just
+                // never gets here for them.
+                if self < Self::ZERO {
+                    self.checked_neg().unwrap_or(<$t>::MAX)
+                } else {
+                    self
+                }
+            }
+
+            fn add_saturating(self, o: Self) -> Self {
+                <$t>::saturating_add(self, o)
+            }
+        }
+
+        impl Integer for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+            const BITS: u32 = <$t>::BITS;
+
+            forward!(
+                checked_add, checked_sub, checked_mul, checked_div, checked_rem
+                => Option<Self>
+            );
+            forward!(
+                wrapping_add, wrapping_sub, wrapping_mul, wrapping_div, wrapping_rem,
+                saturating_add, saturating_sub, saturating_mul, saturating_div
+                => Self
+            );
+        }
+
+        impl Zero for Wrapping<$t> {
+            const ZERO: Self = Wrapping(0);
+        }
+
+        impl One for Wrapping<$t> {
+            const ONE: Self = Wrapping(1);
+        }
+
+        impl Num for Wrapping<$t> {}
+    )*};
+}
+
+impl_integer!(
+    i8, i16, i32 => simd::dot_i32, i64, i128, isize,
+    u8, u16, u32 => simd::dot_u32, u64, u128, usize
+);
+
+macro_rules! impl_float {
+    ($($t:ty => $dot:path, $mul:path);*) => {$(
+        impl Zero for $t {
+            const ZERO: Self = 0.0;
+        }
+
+        impl One for $t {
+            const ONE: Self = 1.0;
+        }
+
+        impl Num for $t {
+            fn dot_kernel() -> Option<DotKernel<Self>> {
+                Some($dot)
+            }
+
+            fn mul_kernel() -> Option<MulKernel<Self>> {
+                Some($mul)
+            }
+
+            fn abs_saturating(self) -> Self {
+                <$t>::abs(self)
+            }
+        }
+
+        impl Field for $t {}
+
+        impl Float for $t {
+            const NAN: Self = <$t>::NAN;
+            const INFINITY: Self = <$t>::INFINITY;
+            const EPSILON: Self = <$t>::EPSILON;
+
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                <$t>::mul_add(self, a, b)
+            }
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn is_nan(self) -> bool {
+                <$t>::is_nan(self)
+            }
+
+            fn sqrt(self) -> Self {
+                <$t>::sqrt(self)
+            }
+
+            fn powf(self, n: Self) -> Self {
+                <$t>::powf(self, n)
+            }
+
+            fn hypot(self, o: Self) -> Self {
+                <$t>::hypot(self, o)
+            }
+
+            fn sin(self) -> Self {
+                <$t>::sin(self)
+            }
+
+            fn cos(self) -> Self {
+                <$t>::cos(self)
+            }
+
+            fn atan2(self, x: Self) -> Self {
+                <$t>::atan2(self, x)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_f64(v: f64) -> Self {
+                v as $t
+            }
+        }
+    )*};
+}
+
+impl_float!(
+    f32 => simd::dot_f32, simd::mul_f32;
+    f64 => simd::dot_f64, simd::mul_f64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn twice<T: Num>(x: T) -> T {
+        x * (T::ONE + T::ONE) + T::ZERO
+    }
+
+    #[test]
+    fn hierarchy_check() {
+        assert_eq!(6, twice(3u8));
+        assert_eq!(-6i128, twice(-3));
+        assert_eq!(1.5f32, twice(0.75));
+        assert_eq!(Wrapping(254u8), twice(Wrapping(127u8)));
+        assert_eq!(Wrapping(0u8), twice(Wrapping(128u8)));
+        assert_eq!(
+            (i16::MIN, 16),
+            (<i16 as Integer>::MIN, <i16 as Integer>::BITS)
+        );
+    }
+}
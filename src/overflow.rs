@@ -0,0 +1,292 @@
+//! Integer slice ops with the overflow behaviour spelled out.
+//!
+//! Plain `mul_slice()` and `dot_slice()` do whatever `*` and `+` do:
+//! panic in debug builds, wrap in release. Pick one of these instead:
+//!
+//! - `checked_*`: stop at the first overflow (or division by zero) and
+//!   tell you where it happened.
+//! - `wrapping_*`: wrap around, in every build.
+//! - `saturating_*`: clamp to the type's MIN / MAX.
+//! - `widening_*`: convert up to a wider type first (say i16 into i64)
+//!   so it can't overflow in the first place.
+//!
+//! Sizes don't have to match; only the shortest slice counts.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::num::{Integer, Num};
+
+/// # Overflow
+/// Error for a checked op that overflowed (or
+/// divided by zero).
+///
+/// `index` is the element where it went wrong.
+/// For a dot product that's the pair whose product,
+/// or whose addition to the running total, overflowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Overflow {
+    pub index: usize,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "integer overflow at index {}", self.index)
+    }
+}
+
+impl Error for Overflow {}
+
+/// Stamps out the checked, wrapping and (where std has it) saturating
+/// versions of an element-wise op. Each `name = method` pair is the
+/// slice function to generate and the `Integer` method it maps over.
+macro_rules! overflow_op {
+    (
+        $op:literal,
+        $checked:ident = $cm:ident,
+        $wrapping:ident = $wm:ident
+        $(, $saturating:ident = $sm:ident)?
+    ) => {
+        #[doc = concat!("# ", stringify!($checked), "()")]
+        #[doc = concat!("Element-wise `", $op, "` of a couple of integer slices,")]
+        /// checked as it goes.
+        ///
+        /// Returns a new shiny Vec\<T\>, or an `Overflow`
+        /// pointing at the first element that overflowed
+        /// or divided by zero.
+        pub fn $checked<T: Integer>(a: &[T], b: &[T]) -> Result<Vec<T>, Overflow> {
+            a.iter()
+                .zip(b)
+                .enumerate()
+                .map(|(index, (&x, &y))| x.$cm(y).ok_or(Overflow { index }))
+                .collect()
+        }
+
+        #[doc = concat!("# ", stringify!($wrapping), "()")]
+        #[doc = concat!("Element-wise `", $op, "` of a couple of integer slices,")]
+        /// wrapping around on overflow in every build.
+        ///
+        /// Returns a new shiny Vec\<T\>. Dividing by zero
+        /// still panics.
+        pub fn $wrapping<T: Integer>(a: &[T], b: &[T]) -> Vec<T> {
+            a.iter().zip(b).map(|(&x, &y)| x.$wm(y)).collect()
+        }
+
+        $(
+            #[doc = concat!("# ", stringify!($saturating), "()")]
+            #[doc = concat!("Element-wise `", $op, "` of a couple of integer slices,")]
+            /// clamped to the type's MIN / MAX.
+            ///
+            /// Returns a new shiny Vec\<T\>. Dividing by zero
+            /// still panics.
+            pub fn $saturating<T: Integer>(a: &[T], b: &[T]) -> Vec<T> {
+                a.iter().zip(b).map(|(&x, &y)| x.$sm(y)).collect()
+            }
+        )?
+    };
+}
+
+overflow_op!(
+    "+",
+    checked_add_slice = checked_add,
+    wrapping_add_slice = wrapping_add,
+    saturating_add_slice = saturating_add
+);
+overflow_op!(
+    "-",
+    checked_sub_slice = checked_sub,
+    wrapping_sub_slice = wrapping_sub,
+    saturating_sub_slice = saturating_sub
+);
+overflow_op!(
+    "*",
+    checked_mul_slice = checked_mul,
+    wrapping_mul_slice = wrapping_mul,
+    saturating_mul_slice = saturating_mul
+);
+overflow_op!(
+    "/",
+    checked_div_slice = checked_div,
+    wrapping_div_slice = wrapping_div,
+    saturating_div_slice = saturating_div
+);
+overflow_op!(
+    "%",
+    checked_rem_slice = checked_rem,
+    wrapping_rem_slice = wrapping_rem
+);
+
+/// # checked_dot_slice()
+/// Takes a reference to a couple of integer slices.
+///
+/// Returns their dot product, or an `Overflow`
+/// pointing at the pair where a product or the
+/// running total overflowed.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::overflow::{checked_dot_slice, Overflow};
+///
+/// assert_eq!(Ok(110), checked_dot_slice(&[10u8, 1], &[10, 10]));
+/// assert_eq!(Err(Overflow { index: 1 }), checked_dot_slice(&[10u8, 16], &[10, 10]));
+/// ```
+pub fn checked_dot_slice<T: Integer>(a: &[T], b: &[T]) -> Result<T, Overflow> {
+    let mut acc = T::ZERO;
+    for (index, (&x, &y)) in a.iter().zip(b).enumerate() {
+        acc = x
+            .checked_mul(y)
+            .and_then(|p| acc.checked_add(p))
+            .ok_or(Overflow { index })?;
+    }
+    Ok(acc)
+}
+
+/// # wrapping_dot_slice()
+/// Takes a reference to a couple of integer slices.
+///
+/// Returns their dot product, wrapping around on
+/// overflow in every build.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::overflow::wrapping_dot_slice;
+///
+/// assert_eq!(4, wrapping_dot_slice(&[10u8, 16], &[10, 10]));
+/// ```
+pub fn wrapping_dot_slice<T: Integer>(a: &[T], b: &[T]) -> T {
+    a.iter()
+        .zip(b)
+        .fold(T::ZERO, |acc, (&x, &y)| acc.wrapping_add(x.wrapping_mul(y)))
+}
+
+/// # saturating_dot_slice()
+/// Takes a reference to a couple of integer slices.
+///
+/// Returns their dot product with every product
+/// and every addition clamped to MIN / MAX. Once
+/// it's pinned at a limit, later terms can still
+/// pull it back, so the order of the slices matters.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::overflow::saturating_dot_slice;
+///
+/// assert_eq!(255, saturating_dot_slice(&[10u8, 16], &[10, 10]));
+/// ```
+pub fn saturating_dot_slice<T: Integer>(a: &[T], b: &[T]) -> T {
+    a.iter().zip(b).fold(T::ZERO, |acc, (&x, &y)| {
+        acc.saturating_add(x.saturating_mul(y))
+    })
+}
+
+/// # widening_dot_slice()
+/// Takes a reference to a couple of slices and
+/// works out their dot product in the wider type
+/// `W` you ask for.
+///
+/// Every element gets converted up before it's
+/// multiplied, so i16 inputs into an i64 total
+/// can't overflow on any realistic length.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::overflow::widening_dot_slice;
+///
+/// let t = [i16::MAX; 4];
+/// let q: i64 = widening_dot_slice(&t, &t);
+/// assert_eq!(4 * 32767 * 32767, q);
+/// ```
+pub fn widening_dot_slice<T: Copy, W: Num + From<T>>(a: &[T], b: &[T]) -> W {
+    a.iter()
+        .zip(b)
+        .fold(W::ZERO, |acc, (&x, &y)| acc + W::from(x) * W::from(y))
+}
+
+/// Stamps out a widening element-wise op.
+macro_rules! widening_op {
+    ($op:literal, $name:ident, |$x:ident, $y:ident| $body:expr) => {
+        #[doc = concat!("# ", stringify!($name), "()")]
+        #[doc = concat!("Element-wise `", $op, "` of a couple of slices, worked")]
+        /// out in the wider type `W` you ask for.
+        ///
+        /// Returns a new shiny Vec\<W\>. Only goes up to
+        /// the size of the smallest slice.
+        pub fn $name<T: Copy, W: Num + From<T>>(a: &[T], b: &[T]) -> Vec<W> {
+            a.iter()
+                .zip(b)
+                .map(|(&x, &y)| {
+                    let ($x, $y) = (W::from(x), W::from(y));
+                    $body
+                })
+                .collect()
+        }
+    };
+}
+
+widening_op!("+", widening_add_slice, |x, y| x + y);
+widening_op!("-", widening_sub_slice, |x, y| x - y);
+widening_op!("*", widening_mul_slice, |x, y| x * y);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_check() {
+        let t = [100i8, -100, 1];
+        let u = [27i8, 29, 0];
+        assert_eq!(Ok(vec![127, -71]), checked_add_slice(&t[..2], &u));
+        assert_eq!(Err(Overflow { index: 1 }), checked_sub_slice(&t, &u));
+        assert_eq!(Err(Overflow { index: 0 }), checked_mul_slice(&t, &u));
+        assert_eq!(Err(Overflow { index: 2 }), checked_div_slice(&t, &u));
+        assert_eq!(
+            Err(Overflow { index: 0 }),
+            checked_div_slice(&[i8::MIN], &[-1])
+        );
+        assert_eq!(Ok(vec![19, -16]), checked_rem_slice(&t, &[27, 28]));
+        assert_eq!(
+            "integer overflow at index 2",
+            Overflow { index: 2 }.to_string()
+        );
+    }
+
+    #[test]
+    fn wrapping_saturating_check() {
+        let t = [200u8, 3, 0];
+        let u = [100u8, 4];
+        assert_eq!(vec![44, 7], wrapping_add_slice(&t, &u));
+        assert_eq!(vec![100, 255], wrapping_sub_slice(&t, &u));
+        assert_eq!(vec![32, 12], wrapping_mul_slice(&t, &u));
+        assert_eq!(vec![2, 0], wrapping_div_slice(&t, &u));
+        assert_eq!(vec![0, 3], wrapping_rem_slice(&t, &u));
+        assert_eq!(vec![255, 7], saturating_add_slice(&t, &u));
+        assert_eq!(vec![100, 0], saturating_sub_slice(&t, &u));
+        assert_eq!(vec![255, 12], saturating_mul_slice(&t, &u));
+        assert_eq!(vec![127], saturating_div_slice(&[i8::MIN], &[-1]));
+    }
+
+    #[test]
+    fn dot_check() {
+        let t = [i32::MAX, 1, -2];
+        let u = [1, 1, 1];
+        assert_eq!(Err(Overflow { index: 1 }), checked_dot_slice(&t, &u));
+        assert_eq!(Ok(i32::MAX), checked_dot_slice(&t[..1], &u));
+        assert_eq!(i32::MAX - 1, wrapping_dot_slice(&t, &u));
+        assert_eq!(i32::MAX - 2, saturating_dot_slice(&t, &u));
+        assert_eq!(0i32, checked_dot_slice::<i32>(&[], &[1]).unwrap());
+    }
+
+    #[test]
+    fn widening_check() {
+        let t = [u8::MAX, 1];
+        let u = [u8::MAX, 2, 3];
+        assert_eq!(vec![510u16, 3], widening_add_slice(&t, &u));
+        assert_eq!(vec![0i16, -1], widening_sub_slice(&t, &u));
+        assert_eq!(vec![65025u32, 2], widening_mul_slice(&t, &u));
+        let q: u64 = widening_dot_slice(&t, &u);
+        assert_eq!(65027, q);
+        let big = vec![i16::MIN; 1000];
+        let q: i64 = widening_dot_slice(&big, &big);
+        assert_eq!(1000 * 32768 * 32768, q);
+    }
+}
@@ -0,0 +1,214 @@
+//! Rayon-backed versions of the big slice ops, behind the `parallel`
+//! cargo feature.
+//!
+//! Slices are always cut into `PAR_CHUNK` sized pieces, however many
+//! threads there are, and the per-chunk results are combined left to
+//! right on one thread. So on a given machine float results don't
+//! depend on the thread count. They can still differ between machines
+//! (the SIMD kernels are picked at runtime and their lane widths vary
+//! with the CPU), and needn't match the serial function bit for bit,
+//! since that adds in a different order.
+//!
+//! Anything shorter than `PAR_THRESHOLD` just runs the serial path;
+//! it's not worth waking the thread pool for.
+
+use rayon::prelude::*;
+
+use crate::num::Num;
+use crate::reduce::{max_slice_value, min_slice_value};
+use crate::{add_slice_into, dot_slice, mul_slice_into};
+
+/// Slices shorter than this skip the thread pool.
+pub const PAR_THRESHOLD: usize = 1 << 16;
+
+/// Every parallel op works in pieces of this many elements.
+pub const PAR_CHUNK: usize = 1 << 14;
+
+/// Per-chunk results, in chunk order.
+fn chunked<T, R>(a: &[T], b: &[T], f: impl Fn(&[T], &[T]) -> R + Sync) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    a.par_chunks(PAR_CHUNK)
+        .zip(b.par_chunks(PAR_CHUNK))
+        .map(|(x, y)| f(x, y))
+        .collect()
+}
+
+/// # par_dot_slice()
+/// Same as `dot_slice()`, spread over the rayon
+/// thread pool for big slices.
+///
+/// Only calculates for the shortest slice.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::parallel::par_dot_slice;
+///
+/// let t = vec![0.5f64; 1_000_000];
+/// let u = vec![2.0f64; 1_000_000];
+/// assert_eq!(1_000_000.0, par_dot_slice(&t, &u));
+/// ```
+pub fn par_dot_slice<T: Num + Send + Sync>(a: &[T], b: &[T]) -> T {
+    let len = a.len().min(b.len());
+    if len < PAR_THRESHOLD {
+        return dot_slice(a, b);
+    }
+    chunked(&a[..len], &b[..len], dot_slice)
+        .into_iter()
+        .fold(T::ZERO, |acc, x| acc + x)
+}
+
+/// # par_mul_slice()
+/// Same as `mul_slice()`, spread over the rayon
+/// thread pool for big slices.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::parallel::par_mul_slice;
+///
+/// let t: Vec<i64> = (0..100_000).collect();
+/// let q = par_mul_slice(&t, &t);
+/// assert_eq!(99_999 * 99_999, q[99_999]);
+/// ```
+pub fn par_mul_slice<T: Num + Send + Sync>(a: &[T], b: &[T]) -> Vec<T> {
+    par_elementwise(a, b, mul_slice_into)
+}
+
+/// # par_add_slice()
+/// Same as `add_slice()`, spread over the rayon
+/// thread pool for big slices.
+pub fn par_add_slice<T: Num + Send + Sync>(a: &[T], b: &[T]) -> Vec<T> {
+    par_elementwise(a, b, add_slice_into)
+}
+
+fn par_elementwise<T: Num + Send + Sync>(
+    a: &[T],
+    b: &[T],
+    into: fn(&[T], &[T], &mut [T]) -> usize,
+) -> Vec<T> {
+    let len = a.len().min(b.len());
+    let mut out = a[..len].to_vec();
+    if len < PAR_THRESHOLD {
+        into(a, b, &mut out);
+        return out;
+    }
+    out.par_chunks_mut(PAR_CHUNK)
+        .zip(a.par_chunks(PAR_CHUNK).zip(b.par_chunks(PAR_CHUNK)))
+        .for_each(|(o, (x, y))| {
+            into(x, y, o);
+        });
+    out
+}
+
+/// # par_sum_slice()
+/// Adds up a slice on the rayon thread pool.
+///
+/// Returns `None` for an empty slice, like
+/// `reduce::sum_slice()`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::parallel::par_sum_slice;
+///
+/// let t = vec![1u64; 200_000];
+/// assert_eq!(Some(200_000), par_sum_slice(&t));
+/// ```
+pub fn par_sum_slice<T: Num + Send + Sync>(a: &[T]) -> Option<T> {
+    if a.is_empty() {
+        return None;
+    }
+    let sum = |x: &[T]| x.iter().fold(T::ZERO, |acc, &v| acc + v);
+    if a.len() < PAR_THRESHOLD {
+        return Some(sum(a));
+    }
+    let parts: Vec<T> = a.par_chunks(PAR_CHUNK).map(sum).collect();
+    Some(sum(&parts))
+}
+
+/// # par_min_slice_value()
+/// Same as `reduce::min_slice_value()`, on the
+/// rayon thread pool. A NaN anywhere still wins.
+pub fn par_min_slice_value<T: PartialOrd + Copy + Send + Sync>(a: &[T]) -> Option<T> {
+    if a.len() < PAR_THRESHOLD {
+        return min_slice_value(a);
+    }
+    let parts: Vec<T> = a
+        .par_chunks(PAR_CHUNK)
+        .filter_map(min_slice_value)
+        .collect();
+    min_slice_value(&parts)
+}
+
+/// # par_max_slice_value()
+/// Same as `reduce::max_slice_value()`, on the
+/// rayon thread pool. A NaN anywhere still wins.
+pub fn par_max_slice_value<T: PartialOrd + Copy + Send + Sync>(a: &[T]) -> Option<T> {
+    if a.len() < PAR_THRESHOLD {
+        return max_slice_value(a);
+    }
+    let parts: Vec<T> = a
+        .par_chunks(PAR_CHUNK)
+        .filter_map(max_slice_value)
+        .collect();
+    max_slice_value(&parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mul_slice;
+
+    fn wobble(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| ((i * 7919) % 1000) as f64 * 0.001 - 0.5)
+            .collect()
+    }
+
+    #[test]
+    fn deterministic_check() {
+        let t = wobble(PAR_THRESHOLD * 5 + 3);
+        let u = wobble(PAR_THRESHOLD * 5 + 11);
+        let first = par_dot_slice(&t, &u);
+        for _ in 0..5 {
+            assert_eq!(first.to_bits(), par_dot_slice(&t, &u).to_bits());
+        }
+        assert!((first - dot_slice(&t, &u)).abs() < 1e-6);
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        assert_eq!(
+            first.to_bits(),
+            pool.install(|| par_dot_slice(&t, &u)).to_bits()
+        );
+    }
+
+    #[test]
+    fn elementwise_check() {
+        let t: Vec<i64> = (0..PAR_THRESHOLD as i64 * 2).collect();
+        let u: Vec<i64> = (0..PAR_THRESHOLD as i64 * 3).rev().collect();
+        assert_eq!(mul_slice(&t, &u), par_mul_slice(&t, &u));
+        assert_eq!(crate::add_slice(&t, &u), par_add_slice(&t, &u));
+        assert_eq!(vec![0, 1, 4], par_mul_slice(&[0, 1, 2], &[0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn reduction_check() {
+        let mut t = wobble(PAR_THRESHOLD * 2);
+        t[12345] = -7.0;
+        t[PAR_THRESHOLD + 1] = 9.0;
+        assert_eq!(Some(-7.0), par_min_slice_value(&t));
+        assert_eq!(Some(9.0), par_max_slice_value(&t));
+        t[99] = f64::NAN;
+        assert!(par_min_slice_value(&t).unwrap().is_nan());
+
+        let n: Vec<u64> = (1..=PAR_THRESHOLD as u64 * 3).collect();
+        let e = n.len() as u64 * (n.len() as u64 + 1) / 2;
+        assert_eq!(Some(e), par_sum_slice(&n));
+        assert_eq!(None, par_sum_slice::<u64>(&[]));
+        assert_eq!(None, par_max_slice_value::<u64>(&[]));
+    }
+}
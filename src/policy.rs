@@ -0,0 +1,241 @@
+use crate::num::Num;
+use crate::{dot_slice, mul_slice};
+use std::error::Error;
+use std::fmt;
+
+/// # LengthPolicy
+/// What to do when a couple of slices don't
+/// match in size.
+///
+/// `Truncate` is what `mul_slice()` and friends
+/// have always done: stop at the shortest slice.
+///
+/// `Strict` refuses to play and hands back a
+/// `LengthMismatch` instead.
+///
+/// `PadWith(value)` stretches the shorter slice
+/// out to the longer one using `value`.
+///
+/// `Cycle` repeats the shorter slice over and over
+/// until it covers the longer one (broadcasting).
+/// Cycling an empty slice is a `LengthMismatch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LengthPolicy<T> {
+    #[default]
+    Truncate,
+    Strict,
+    PadWith(T),
+    Cycle,
+}
+
+/// # LengthMismatch
+/// Error for slices that didn't line up under
+/// the `LengthPolicy` you asked for.
+///
+/// Carries both lengths so you can see which
+/// buffer came up short.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LengthMismatch {
+    pub left: usize,
+    pub right: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "slice lengths don't match: left is {}, right is {}",
+            self.left, self.right
+        )
+    }
+}
+
+impl Error for LengthMismatch {}
+
+/// Walks a couple of slices pairwise following a `LengthPolicy`.
+pub(crate) struct Paired<'a, T> {
+    a: &'a [T],
+    b: &'a [T],
+    policy: LengthPolicy<T>,
+    len: usize,
+    i: usize,
+}
+
+impl<'a, T: Copy> Paired<'a, T> {
+    pub(crate) fn new(
+        a: &'a [T],
+        b: &'a [T],
+        policy: LengthPolicy<T>,
+    ) -> Result<Self, LengthMismatch> {
+        let err = LengthMismatch {
+            left: a.len(),
+            right: b.len(),
+        };
+        let len = match policy {
+            LengthPolicy::Truncate => a.len().min(b.len()),
+            LengthPolicy::Strict if a.len() != b.len() => return Err(err),
+            LengthPolicy::Cycle if a.len() != b.len() && (a.is_empty() || b.is_empty()) => {
+                return Err(err)
+            }
+            _ => a.len().max(b.len()),
+        };
+        Ok(Paired {
+            a,
+            b,
+            policy,
+            len,
+            i: 0,
+        })
+    }
+
+    fn pick(&self, s: &[T]) -> T {
+        match self.policy {
+            LengthPolicy::PadWith(v) if self.i >= s.len() => v,
+            LengthPolicy::Cycle => s[self.i % s.len()],
+            _ => s[self.i],
+        }
+    }
+}
+
+impl<T: Copy> Iterator for Paired<'_, T> {
+    type Item = (T, T);
+
+    fn next(&mut self) -> Option<(T, T)> {
+        if self.i >= self.len {
+            return None;
+        }
+        let pair = (self.pick(self.a), self.pick(self.b));
+        self.i += 1;
+        Some(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.len - self.i;
+        (n, Some(n))
+    }
+}
+
+impl<T: Copy> ExactSizeIterator for Paired<'_, T> {}
+
+/// # try_mul_slice()
+/// Takes a reference to a couple slices of
+/// type \<T\> and a `LengthPolicy`.
+///
+/// Returns a new shiny Vec\<T\> of both
+/// slices multiplied together, or a
+/// `LengthMismatch` if the policy says no.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::{try_mul_slice, LengthMismatch, LengthPolicy};
+///
+/// let t = [1, 2, 3];
+/// let u = [10, 20];
+/// let e = LengthMismatch { left: 3, right: 2 };
+/// assert_eq!(Err(e), try_mul_slice(&t, &u, LengthPolicy::Strict));
+/// assert_eq!(Ok(vec![10, 40, 0]), try_mul_slice(&t, &u, LengthPolicy::PadWith(0)));
+/// assert_eq!(Ok(vec![10, 40, 30]), try_mul_slice(&t, &u, LengthPolicy::Cycle));
+/// ```
+pub fn try_mul_slice<T: Num>(
+    a: &[T],
+    b: &[T],
+    policy: LengthPolicy<T>,
+) -> Result<Vec<T>, LengthMismatch> {
+    match policy {
+        LengthPolicy::Truncate | LengthPolicy::Strict => {
+            Paired::new(a, b, policy)?;
+            Ok(mul_slice(a, b))
+        }
+        _ => Ok(Paired::new(a, b, policy)?.map(|(x, y)| x * y).collect()),
+    }
+}
+
+/// # try_dot_slice()
+/// Takes a reference to a couple of slices
+/// and a `LengthPolicy`.
+///
+/// Returns dot product of type \<T\>, or a
+/// `LengthMismatch` if the policy says no.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::{try_dot_slice, LengthPolicy};
+///
+/// let t = [1, 2, 3];
+/// let u = [10, 20];
+/// assert!(try_dot_slice(&t, &u, LengthPolicy::Strict).is_err());
+/// assert_eq!(Ok(50), try_dot_slice(&t, &u, LengthPolicy::Truncate));
+/// assert_eq!(Ok(80), try_dot_slice(&t, &u, LengthPolicy::Cycle));
+/// ```
+pub fn try_dot_slice<T: Num>(
+    a: &[T],
+    b: &[T],
+    policy: LengthPolicy<T>,
+) -> Result<T, LengthMismatch> {
+    match policy {
+        LengthPolicy::Truncate | LengthPolicy::Strict => {
+            Paired::new(a, b, policy)?;
+            Ok(dot_slice(a, b))
+        }
+        _ => Ok(Paired::new(a, b, policy)?.fold(T::ZERO, |acc, (x, y)| acc + x * y)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_check() {
+        let t: Vec<_> = (0..10).step_by(2).collect();
+        let u: Vec<_> = (0..10).step_by(4).collect();
+        let e = LengthMismatch { left: 5, right: 3 };
+        assert_eq!(Err(e), try_mul_slice(&t, &u, LengthPolicy::Strict));
+        assert_eq!(Err(e), try_dot_slice(&t, &u, LengthPolicy::Strict));
+        assert_eq!(Ok(120), try_dot_slice(&t, &t, LengthPolicy::Strict));
+        assert_eq!(
+            "slice lengths don't match: left is 5, right is 3",
+            e.to_string()
+        );
+    }
+
+    #[test]
+    fn truncate_check() {
+        let t: Vec<_> = (0..10).step_by(2).collect();
+        let u: Vec<_> = (0..10).step_by(4).collect();
+        assert_eq!(
+            Ok(vec![0, 8, 32]),
+            try_mul_slice(&t, &u, LengthPolicy::default())
+        );
+        assert_eq!(Ok(40), try_dot_slice(&t, &u, LengthPolicy::Truncate));
+    }
+
+    #[test]
+    fn pad_check() {
+        let t = [1.0, 2.0, 3.0];
+        let u = [2.0];
+        assert_eq!(
+            Ok(vec![2.0, 2.0, 3.0]),
+            try_mul_slice(&u, &t, LengthPolicy::PadWith(1.0))
+        );
+        assert_eq!(Ok(7.0), try_dot_slice(&t, &u, LengthPolicy::PadWith(1.0)));
+    }
+
+    #[test]
+    fn cycle_check() {
+        let t = [1, 2, 3, 4, 5];
+        let u = [1, -1];
+        assert_eq!(
+            Ok(vec![1, -2, 3, -4, 5]),
+            try_mul_slice(&t, &u, LengthPolicy::Cycle)
+        );
+        assert_eq!(Ok(3), try_dot_slice(&u, &t, LengthPolicy::Cycle));
+
+        let e = LengthMismatch { left: 5, right: 0 };
+        assert_eq!(Err(e), try_mul_slice(&t, &[], LengthPolicy::Cycle));
+        assert_eq!(
+            Ok(vec![]),
+            try_mul_slice::<i32>(&[], &[], LengthPolicy::Cycle)
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Slices as polynomials.
+//!
+//! Coefficients go lowest power first, so `c[k]` multiplies `x^k` and
+//! `[1, 0, 3]` is `1 + 3x²`; that's NumPy's `numpy.polynomial` order
+//! (not the older `polyval()`'s). The zero polynomial is the empty
+//! slice.
+//!
+//! Arithmetic keeps every coefficient it's given: nothing trims zeros
+//! off the top, so `add(&[1, 2], &[0, -2])` is `[1, 0]`.
+
+use std::cmp::Ordering;
+
+use crate::complex::Complex;
+use crate::convolve::{convolve_direct, ConvolveMode};
+use crate::linalg::{LinalgError, Qr};
+use crate::norm::l2_norm;
+use crate::num::{Float, Num, One, Zero};
+
+/// Durand–Kerner sweeps before giving up.
+const MAX_SWEEPS: usize = 500;
+
+/// # eval()
+/// Takes a reference to a polynomial's
+/// coefficients and a point.
+///
+/// Returns the polynomial's value there,
+/// by Horner's rule.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::eval;
+///
+/// // 1 + 2x + 3x² at x = 2
+/// assert_eq!(17, eval(&[1, 2, 3], 2));
+/// assert_eq!(0, eval(&[], 5));
+/// ```
+pub fn eval<T: Num>(c: &[T], x: T) -> T {
+    c.iter().rev().fold(T::ZERO, |acc, &k| acc * x + k)
+}
+
+/// # eval_slice()
+/// Takes a reference to a polynomial's
+/// coefficients and a slice of points.
+///
+/// Returns a new shiny Vec\<T\> of the
+/// polynomial's value at each point.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::eval_slice;
+///
+/// assert_eq!(vec![1, 2, 5], eval_slice(&[1, 0, 1], &[0, 1, 2]));
+/// ```
+pub fn eval_slice<T: Num>(c: &[T], xs: &[T]) -> Vec<T> {
+    xs.iter().map(|&x| eval(c, x)).collect()
+}
+
+/// Applies `f` coefficient by coefficient, treating the shorter
+/// side as zero-padded.
+fn zip_longest<T: Num>(a: &[T], b: &[T], f: impl Fn(T, T) -> T) -> Vec<T> {
+    (0..a.len().max(b.len()))
+        .map(|k| {
+            let x = a.get(k).copied().unwrap_or(T::ZERO);
+            let y = b.get(k).copied().unwrap_or(T::ZERO);
+            f(x, y)
+        })
+        .collect()
+}
+
+/// # add()
+/// Takes references to two polynomials.
+///
+/// Returns their sum as a new shiny Vec\<T\>,
+/// as long as the longer of the two.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::add;
+///
+/// assert_eq!(vec![2, 2, 3], add(&[1, 2, 3], &[1]));
+/// ```
+pub fn add<T: Num>(a: &[T], b: &[T]) -> Vec<T> {
+    zip_longest(a, b, |x, y| x + y)
+}
+
+/// # sub()
+/// Takes references to two polynomials.
+///
+/// Returns `a - b` as a new shiny Vec\<T\>,
+/// as long as the longer of the two.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::sub;
+///
+/// assert_eq!(vec![0, 2, -3], sub(&[1, 2], &[1, 0, 3]));
+/// ```
+pub fn sub<T: Num>(a: &[T], b: &[T]) -> Vec<T> {
+    zip_longest(a, b, |x, y| x - y)
+}
+
+/// # mul()
+/// Takes references to two polynomials.
+///
+/// Returns their product as a new shiny
+/// Vec\<T\>, `a.len() + b.len() - 1` long
+/// (empty if either is). Multiplying
+/// polynomials is convolving their
+/// coefficients, so this is
+/// `convolve_direct()` in `Full` mode.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::mul;
+///
+/// // (1 + x)(1 - x) = 1 - x²
+/// assert_eq!(vec![1, 0, -1], mul(&[1, 1], &[1, -1]));
+/// ```
+pub fn mul<T: Num>(a: &[T], b: &[T]) -> Vec<T> {
+    convolve_direct(a, b, ConvolveMode::Full)
+}
+
+/// # derivative()
+/// Takes a reference to a polynomial.
+///
+/// Returns its derivative as a new shiny
+/// Vec\<T\>, one coefficient shorter.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::derivative;
+///
+/// // d/dx (1 + 2x + 3x²) = 2 + 6x
+/// assert_eq!(vec![2, 6], derivative(&[1, 2, 3]));
+/// ```
+pub fn derivative<T: Num>(c: &[T]) -> Vec<T> {
+    let mut k = T::ZERO;
+    c.iter()
+        .skip(1)
+        .map(|&x| {
+            k = k + T::ONE;
+            k * x
+        })
+        .collect()
+}
+
+/// # integral()
+/// Takes a reference to a polynomial and the
+/// constant of integration.
+///
+/// Returns its antiderivative as a new shiny
+/// Vec\<T\>, one coefficient longer, with
+/// `constant` as the value at zero.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::integral;
+///
+/// // ∫ (2 + 6x) dx = 5 + 2x + 3x²
+/// assert_eq!(vec![5.0, 2.0, 3.0], integral(&[2.0, 6.0], 5.0));
+/// ```
+pub fn integral<T: Float>(c: &[T], constant: T) -> Vec<T> {
+    let mut k = T::ZERO;
+    std::iter::once(constant)
+        .chain(c.iter().map(|&x| {
+            k = k + T::ONE;
+            x / k
+        }))
+        .collect()
+}
+
+/// # div_rem()
+/// Takes references to a numerator and a
+/// denominator polynomial.
+///
+/// Returns `Some((quotient, remainder))` with
+/// `num = quotient · den + remainder` and the
+/// remainder shorter than `den`, or None if
+/// `den` is all zeros. Zeros on top of `den`
+/// don't count.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::div_rem;
+///
+/// // x² + 3x + 5 = (x + 1)(x + 2) + 3
+/// let (q, r) = div_rem(&[5.0, 3.0, 1.0], &[1.0, 1.0]).unwrap();
+/// assert_eq!((vec![2.0, 1.0], vec![3.0]), (q, r));
+/// assert_eq!(None, div_rem(&[1.0], &[0.0]));
+/// ```
+pub fn div_rem<T: Float>(num: &[T], den: &[T]) -> Option<(Vec<T>, Vec<T>)> {
+    let m = den.iter().rposition(|&x| x != T::ZERO)? + 1;
+    let den = &den[..m];
+    let mut r = num.to_vec();
+    if num.len() < m {
+        return Some((Vec::new(), r));
+    }
+    let mut q = vec![T::ZERO; num.len() - m + 1];
+    for k in (0..q.len()).rev() {
+        q[k] = r[k + m - 1] / den[m - 1];
+        for (j, &d) in den.iter().enumerate() {
+            r[k + j] = r[k + j] - q[k] * d;
+        }
+    }
+    r.truncate(m - 1);
+    Some((q, r))
+}
+
+/// # fit()
+/// Takes references to the x and y values of
+/// some points and the degree you want.
+///
+/// Returns the coefficients of the polynomial
+/// of that degree closest to the points in the
+/// least-squares sense. Only as many points as
+/// the shorter slice count, and it takes at
+/// least `degree + 1` of them at different x.
+///
+/// Solved by QR on the Vandermonde matrix with
+/// its columns scaled to unit length first,
+/// which keeps it usable a fair way further up
+/// in degree.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::fit;
+///
+/// let x = [0.0, 1.0, 2.0, 3.0];
+/// let y = [1.0, 3.0, 7.0, 13.0]; // 1 + x + x²
+/// let c = fit(&x, &y, 2).unwrap();
+/// assert!(c.iter().all(|&k: &f64| (k - 1.0).abs() < 1e-12));
+/// assert!(fit(&x, &y, 4).is_err());
+/// ```
+pub fn fit<T: Float>(x: &[T], y: &[T], degree: usize) -> Result<Vec<T>, LinalgError> {
+    let (rows, cols) = (x.len().min(y.len()), degree + 1);
+    let mut v = vec![T::ZERO; rows * cols];
+    for (i, &xi) in x[..rows].iter().enumerate() {
+        let mut p = T::ONE;
+        for j in 0..cols {
+            v[i * cols + j] = p;
+            p = p * xi;
+        }
+    }
+    let scale: Vec<T> = (0..cols)
+        .map(|j| {
+            let col: Vec<T> = (0..rows).map(|i| v[i * cols + j]).collect();
+            match l2_norm(&col) {
+                n if n == T::ZERO => T::ONE,
+                n => n,
+            }
+        })
+        .collect();
+    for row in v.chunks_exact_mut(cols) {
+        for (x, &s) in row.iter_mut().zip(&scale) {
+            *x = *x / s;
+        }
+    }
+    let c = Qr::new(&v, rows, cols)?.solve_least_squares(&y[..rows])?;
+    Ok(c.iter().zip(&scale).map(|(&c, &s)| c / s).collect())
+}
+
+/// # roots()
+/// Takes a reference to a polynomial.
+///
+/// Returns all its roots, repeated ones as
+/// many times as they repeat, as a new shiny
+/// Vec\<Complex\<T\>\> sorted by real part then
+/// imaginary part. Constants (and the zero
+/// polynomial) have none.
+///
+/// Found by Durand–Kerner iteration in f64,
+/// run until every root's residual is down to
+/// what rounding in evaluating the polynomial
+/// can explain. Real roots come back with an
+/// imaginary part on the order of rounding
+/// error, and repeated roots are only good to
+/// about the square (cube, ...) root of `ε`.
+/// Gives `LinalgError::NoConvergence` if it
+/// doesn't get there.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::poly::roots;
+///
+/// // 1 + x² = (x + i)(x - i)
+/// let r = roots(&[1.0f64, 0.0, 1.0]).unwrap();
+/// assert!(r[0].re.abs() < 1e-12 && (r[0].im + 1.0).abs() < 1e-12);
+/// assert!(r[1].re.abs() < 1e-12 && (r[1].im - 1.0).abs() < 1e-12);
+/// ```
+pub fn roots<T: Float>(c: &[T]) -> Result<Vec<Complex<T>>, LinalgError> {
+    let top = match c.iter().rposition(|&x| x != T::ZERO) {
+        Some(t) => t,
+        None => return Ok(Vec::new()),
+    };
+    // Zero roots come straight off the bottom.
+    let zeros = c.iter().position(|&x| x != T::ZERO).unwrap_or(0);
+    let a: Vec<f64> = c[zeros..=top].iter().map(|&x| x.to_f64()).collect();
+    let mut r = durand_kerner(&a).ok_or(LinalgError::NoConvergence)?;
+    r.extend(std::iter::repeat_n(Complex::ZERO, zeros));
+    r.sort_by(|a, b| {
+        a.re.partial_cmp(&b.re)
+            .unwrap_or(Ordering::Equal)
+            .then(a.im.partial_cmp(&b.im).unwrap_or(Ordering::Equal))
+    });
+    Ok(r.into_iter()
+        .map(|z| Complex::new(T::from_f64(z.re), T::from_f64(z.im)))
+        .collect())
+}
+
+/// The roots of `a` (lowest power first, nonzero at both ends),
+/// or None if they don't settle.
+fn durand_kerner(a: &[f64]) -> Option<Vec<Complex<f64>>> {
+    let n = a.len() - 1;
+    let lead = a[n];
+    let monic: Vec<Complex<f64>> = a.iter().map(|&x| Complex::from(x / lead)).collect();
+    let radius = 1.0 + monic[..n].iter().map(|z| z.re.abs()).fold(0.0, f64::max);
+    // Horner's rounding error is within about 2n·ε·Σ|a_k|·|z|^k.
+    let slack = 4.0 * n as f64 * f64::EPSILON;
+
+    // The usual starting points: spread round a circle the roots
+    // are inside of, not symmetric so they don't get stuck.
+    let seed = Complex::new(0.4, 0.9);
+    let mut z: Vec<Complex<f64>> = Vec::with_capacity(n);
+    let mut w = Complex::from(radius);
+    for _ in 0..n {
+        z.push(w);
+        w = w * seed;
+    }
+
+    for _ in 0..MAX_SWEEPS {
+        let settled = z.iter().all(|&x| {
+            let p = eval(&monic, x);
+            let bound = monic.iter().rev().fold(0.0, |b, k| b * x.abs() + k.abs());
+            p.abs() <= slack * bound
+        });
+        if settled {
+            return Some(z);
+        }
+        for i in 0..n {
+            let d = (0..n)
+                .filter(|&j| j != i)
+                .fold(Complex::ONE, |d, j| d * (z[i] - z[j]));
+            z[i] = z[i] - eval(&monic, z[i]) / d;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::close;
+
+    #[test]
+    fn arithmetic_check() {
+        let a = [3, 0, -2, 1];
+        let b = [1, 4];
+        assert_eq!(vec![4, 4, -2, 1], add(&a, &b));
+        assert_eq!(add(&b, &a), add(&a, &b));
+        assert_eq!(vec![2, -4, -2, 1], sub(&a, &b));
+        let p = mul(&a, &b);
+        assert_eq!(vec![3, 12, -2, -7, 4], p);
+        for x in -3..4 {
+            assert_eq!(eval(&a, x) * eval(&b, x), eval(&p, x));
+        }
+        assert!(mul(&a, &[]).is_empty());
+        assert_eq!(vec![0, -4, 3], derivative(&a));
+        assert!(derivative(&[7]).is_empty());
+
+        // 1 + z² at z = i
+        let one = Complex::new(1, 0);
+        assert_eq!(
+            Complex::ZERO,
+            eval(&[one, Complex::ZERO, one], Complex::new(0, 1))
+        );
+    }
+
+    #[test]
+    fn calculus_check() {
+        let c = [1.5, -2.0, 0.25, 4.0];
+        assert_eq!(c.to_vec(), derivative(&integral(&c, 9.0)));
+        assert_eq!(9.0, integral(&c, 9.0)[0]);
+        assert_eq!(vec![0.5f32], integral(&[], 0.5f32));
+    }
+
+    #[test]
+    fn div_rem_check() {
+        let num = [-4.0, 0.0, -2.0, 1.0, 0.5];
+        let den = [-3.0, 1.0, 0.0];
+        let (q, r) = div_rem(&num, &den).unwrap();
+        assert_eq!(4, q.len());
+        assert_eq!(1, r.len());
+        let back = add(&mul(&q, &den[..2]), &r);
+        assert!(close(&back, &num, 1e-12));
+
+        let (q, r) = div_rem(&[1.0, 2.0], &[0.0, 0.0, 3.0]).unwrap();
+        assert!(q.is_empty());
+        assert_eq!(vec![1.0, 2.0], r);
+        let (q, r) = div_rem(&[2.0, 4.0], &[2.0]).unwrap();
+        assert_eq!((vec![1.0, 2.0], vec![]), (q, r));
+        assert_eq!(None, div_rem::<f64>(&[1.0], &[]));
+    }
+
+    #[test]
+    fn fit_check() {
+        let x: Vec<f64> = (0..20).map(|i| i as f64 * 0.5 - 3.0).collect();
+        let want = [0.5, -1.0, 0.25, 0.125];
+        let y = eval_slice(&want, &x);
+        assert!(close(&fit(&x, &y, 3).unwrap(), &want, 1e-10));
+
+        // A line through noise that averages out.
+        let noisy: Vec<f64> = x
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| 2.0 * x + 1.0 + if i % 2 == 0 { 0.1 } else { -0.1 })
+            .collect();
+        let c = fit(&x, &noisy, 1).unwrap();
+        assert!(close(&c, &[1.0, 2.0], 0.02));
+
+        assert_eq!(
+            Err(LinalgError::SingularMatrix),
+            fit(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0], 1)
+        );
+    }
+
+    #[test]
+    fn roots_check() {
+        // (x - 1)(x - 2)(x - 3)
+        let r = roots(&[-6.0, 11.0, -6.0, 1.0]).unwrap();
+        let re: Vec<f64> = r.iter().map(|z| z.re).collect();
+        assert!(close(&re, &[1.0, 2.0, 3.0], 1e-10));
+        assert!(r.iter().all(|z| z.im.abs() < 1e-10));
+
+        // x²(x - 1)²(x + 2): zero roots and a double one.
+        let p = mul(&mul(&[0.0, 0.0, 1.0], &[1.0, -2.0, 1.0]), &[2.0, 1.0]);
+        let r = roots(&p).unwrap();
+        assert_eq!(5, r.len());
+        let re: Vec<f64> = r.iter().map(|z| z.re).collect();
+        assert!(close(&re, &[-2.0, 0.0, 0.0, 1.0, 1.0], 1e-6));
+        assert_eq!(Complex::ZERO, r[1]);
+
+        // Every root of a random-ish degree 9 puts the polynomial near zero.
+        let c: Vec<f64> = (0..10).map(|k| ((k * 7 % 5) as f64) - 1.5).collect();
+        for z in roots(&c).unwrap() {
+            let cz: Vec<Complex<f64>> = c.iter().map(|&x| Complex::from(x)).collect();
+            assert!(eval(&cz, z).abs() < 1e-9);
+        }
+
+        let r = roots(&[-2.0f32, 0.0, 1.0]).unwrap();
+        assert!((r[1].re - 2f32.sqrt()).abs() < 1e-6);
+        assert!(roots(&[5.0]).unwrap().is_empty());
+        assert!(roots::<f64>(&[0.0, 0.0]).unwrap().is_empty());
+        assert!(roots(&[3.0, 0.0]).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,395 @@
+//! Squash a slice down to a single value.
+//!
+//! Everything here hands back `None` for an empty slice rather than
+//! panicking or making up a zero.
+
+use std::cmp::Ordering;
+use std::iter::{Product, Sum};
+
+use crate::num::Float;
+
+/// Does `x` refuse to compare with itself? That's a NaN, or something
+/// acting like one.
+fn is_nan_like<T: PartialOrd>(x: &T) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+/// Index of the winner by `better`, with NaN-likes taking over.
+fn arg_by<T: PartialOrd>(a: &[T], better: Ordering) -> Option<usize> {
+    if a.is_empty() {
+        return None;
+    }
+    let mut best = 0;
+    for (i, x) in a.iter().enumerate().skip(1) {
+        if is_nan_like(&a[best]) {
+            break;
+        }
+        if is_nan_like(x) || x.partial_cmp(&a[best]) == Some(better) {
+            best = i;
+        }
+    }
+    Some(best)
+}
+
+/// Index of the winner by `better`, skipping NaNs.
+fn nan_arg_by<T: Float>(a: &[T], better: Ordering) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (i, x) in a.iter().enumerate() {
+        if x.is_nan() {
+            continue;
+        }
+        match best {
+            Some(j) if x.partial_cmp(&a[j]) != Some(better) => {}
+            _ => best = Some(i),
+        }
+    }
+    best
+}
+
+/// # sum_slice()
+/// Takes a reference to a slice.
+///
+/// Returns the sum of everything in it,
+/// or `None` if it's empty.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::sum_slice;
+///
+/// assert_eq!(Some(10), sum_slice(&[1, 2, 3, 4]));
+/// assert_eq!(None, sum_slice::<i32>(&[]));
+/// ```
+pub fn sum_slice<T: for<'a> Sum<&'a T>>(a: &[T]) -> Option<T> {
+    if a.is_empty() {
+        return None;
+    }
+    Some(a.iter().sum())
+}
+
+/// # product_slice()
+/// Takes a reference to a slice.
+///
+/// Returns everything in it multiplied together,
+/// or `None` if it's empty.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::product_slice;
+///
+/// assert_eq!(Some(24), product_slice(&[1, 2, 3, 4]));
+/// assert_eq!(None, product_slice::<i32>(&[]));
+/// ```
+pub fn product_slice<T: for<'a> Product<&'a T>>(a: &[T]) -> Option<T> {
+    if a.is_empty() {
+        return None;
+    }
+    Some(a.iter().product())
+}
+
+/// # mean_slice()
+/// Takes a reference to a float slice.
+///
+/// Returns the average, or `None` if it's empty.
+/// Keeps a running mean instead of a big sum, so
+/// huge values won't overflow to infinity.
+///
+/// Any NaN in there makes the result NaN;
+/// use `nan_mean_slice()` to skip them.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::mean_slice;
+///
+/// assert_eq!(Some(2.5), mean_slice(&[1.0, 2.0, 3.0, 4.0]));
+/// assert_eq!(Some(f64::MAX), mean_slice(&[f64::MAX, f64::MAX]));
+/// ```
+pub fn mean_slice<T: Float>(a: &[T]) -> Option<T> {
+    running_mean(a.iter().copied())
+}
+
+fn running_mean<T: Float>(it: impl Iterator<Item = T>) -> Option<T> {
+    // The count stays a usize: as a float it would stop going up
+    // at 2^24 for f32.
+    let mut mean = T::ZERO;
+    let mut n = 0usize;
+    for x in it {
+        n += 1;
+        mean = mean + (x - mean) / T::from_f64(n as f64);
+    }
+    if n > 0 {
+        Some(mean)
+    } else {
+        None
+    }
+}
+
+/// # min_slice_value()
+/// Takes a reference to a slice.
+///
+/// Returns the smallest value in it, or
+/// `None` if it's empty. A NaN anywhere wins,
+/// so you'll know it's there; use
+/// `nan_min_slice_value()` to skip them.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::min_slice_value;
+///
+/// assert_eq!(Some(-3), min_slice_value(&[4, -3, 7]));
+/// assert!(min_slice_value(&[1.0, f64::NAN]).unwrap().is_nan());
+/// ```
+pub fn min_slice_value<T: PartialOrd + Copy>(a: &[T]) -> Option<T> {
+    arg_by(a, Ordering::Less).map(|i| a[i])
+}
+
+/// # max_slice_value()
+/// Takes a reference to a slice.
+///
+/// Returns the biggest value in it, or
+/// `None` if it's empty. A NaN anywhere wins,
+/// so you'll know it's there; use
+/// `nan_max_slice_value()` to skip them.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::max_slice_value;
+///
+/// assert_eq!(Some(7), max_slice_value(&[4, -3, 7]));
+/// ```
+pub fn max_slice_value<T: PartialOrd + Copy>(a: &[T]) -> Option<T> {
+    arg_by(a, Ordering::Greater).map(|i| a[i])
+}
+
+/// # argmin()
+/// Takes a reference to a slice.
+///
+/// Returns the index of the smallest value (the
+/// first one on ties), or `None` if it's empty.
+/// The first NaN wins if there is one.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::argmin;
+///
+/// assert_eq!(Some(1), argmin(&[4, -3, 7, -3]));
+/// ```
+pub fn argmin<T: PartialOrd>(a: &[T]) -> Option<usize> {
+    arg_by(a, Ordering::Less)
+}
+
+/// # argmax()
+/// Takes a reference to a slice.
+///
+/// Returns the index of the biggest value (the
+/// first one on ties), or `None` if it's empty.
+/// The first NaN wins if there is one.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::argmax;
+///
+/// assert_eq!(Some(2), argmax(&[4, -3, 7, 7]));
+/// ```
+pub fn argmax<T: PartialOrd>(a: &[T]) -> Option<usize> {
+    arg_by(a, Ordering::Greater)
+}
+
+/// # all_slice()
+/// Takes a reference to a slice and a predicate.
+///
+/// Returns `true` if the predicate holds for
+/// every element. An empty slice is `true`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::all_slice;
+///
+/// assert!(all_slice(&[2, 4, 6], |x| x % 2 == 0));
+/// ```
+pub fn all_slice<T>(a: &[T], f: impl FnMut(&T) -> bool) -> bool {
+    a.iter().all(f)
+}
+
+/// # any_slice()
+/// Takes a reference to a slice and a predicate.
+///
+/// Returns `true` if the predicate holds for at
+/// least one element. An empty slice is `false`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::any_slice;
+///
+/// assert!(any_slice(&[1.0, f64::NAN], |x| x.is_nan()));
+/// ```
+pub fn any_slice<T>(a: &[T], f: impl FnMut(&T) -> bool) -> bool {
+    a.iter().any(f)
+}
+
+/// # nan_sum_slice()
+/// Takes a reference to a float slice.
+///
+/// Returns the sum of everything that isn't NaN,
+/// or `None` if there's nothing but NaNs (or nothing).
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::nan_sum_slice;
+///
+/// assert_eq!(Some(3.0), nan_sum_slice(&[1.0, f64::NAN, 2.0]));
+/// assert_eq!(None, nan_sum_slice(&[f64::NAN]));
+/// ```
+pub fn nan_sum_slice<T: Float>(a: &[T]) -> Option<T> {
+    let mut it = a.iter().filter(|x| !x.is_nan()).peekable();
+    it.peek()?;
+    Some(it.sum())
+}
+
+/// # nan_mean_slice()
+/// Takes a reference to a float slice.
+///
+/// Returns the average of everything that isn't NaN,
+/// or `None` if there's nothing but NaNs (or nothing).
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::nan_mean_slice;
+///
+/// assert_eq!(Some(2.0), nan_mean_slice(&[1.0, f64::NAN, 3.0]));
+/// ```
+pub fn nan_mean_slice<T: Float>(a: &[T]) -> Option<T> {
+    running_mean(a.iter().copied().filter(|x| !x.is_nan()))
+}
+
+/// # nan_min_slice_value()
+/// Takes a reference to a float slice.
+///
+/// Returns the smallest value that isn't NaN,
+/// or `None` if there's nothing but NaNs (or nothing).
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::nan_min_slice_value;
+///
+/// assert_eq!(Some(1.0), nan_min_slice_value(&[f64::NAN, 1.0, 3.0]));
+/// ```
+pub fn nan_min_slice_value<T: Float>(a: &[T]) -> Option<T> {
+    nan_arg_by(a, Ordering::Less).map(|i| a[i])
+}
+
+/// # nan_max_slice_value()
+/// Takes a reference to a float slice.
+///
+/// Returns the biggest value that isn't NaN,
+/// or `None` if there's nothing but NaNs (or nothing).
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::nan_max_slice_value;
+///
+/// assert_eq!(Some(3.0), nan_max_slice_value(&[f64::NAN, 1.0, 3.0]));
+/// ```
+pub fn nan_max_slice_value<T: Float>(a: &[T]) -> Option<T> {
+    nan_arg_by(a, Ordering::Greater).map(|i| a[i])
+}
+
+/// # nan_argmin()
+/// Takes a reference to a float slice.
+///
+/// Returns the index of the smallest value that
+/// isn't NaN, or `None` if there isn't one.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::nan_argmin;
+///
+/// assert_eq!(Some(2), nan_argmin(&[f64::NAN, 3.0, 1.0]));
+/// ```
+pub fn nan_argmin<T: Float>(a: &[T]) -> Option<usize> {
+    nan_arg_by(a, Ordering::Less)
+}
+
+/// # nan_argmax()
+/// Takes a reference to a float slice.
+///
+/// Returns the index of the biggest value that
+/// isn't NaN, or `None` if there isn't one.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::reduce::nan_argmax;
+///
+/// assert_eq!(Some(1), nan_argmax(&[f64::NAN, 3.0, 1.0]));
+/// ```
+pub fn nan_argmax<T: Float>(a: &[T]) -> Option<usize> {
+    nan_arg_by(a, Ordering::Greater)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_check() {
+        let e: [f64; 0] = [];
+        assert_eq!(None, sum_slice(&e));
+        assert_eq!(None, product_slice(&e));
+        assert_eq!(None, mean_slice(&e));
+        assert_eq!(None, min_slice_value(&e));
+        assert_eq!(None, max_slice_value(&e));
+        assert_eq!(None, argmin(&e));
+        assert_eq!(None, argmax(&e));
+        assert_eq!(None, nan_sum_slice(&e));
+        assert_eq!(None, nan_mean_slice(&e));
+        assert_eq!(None, nan_argmin(&e));
+        assert_eq!(None, nan_argmax(&e));
+        assert!(all_slice(&e, |_| false));
+        assert!(!any_slice(&e, |_| true));
+    }
+
+    #[test]
+    fn integer_check() {
+        let t: Vec<_> = (0..10).step_by(2).collect();
+        assert_eq!(Some(20), sum_slice(&t));
+        assert_eq!(Some(0), product_slice(&t));
+        assert_eq!(Some(384), product_slice(&t[1..]));
+        assert_eq!(Some(0), min_slice_value(&t));
+        assert_eq!(Some(8), max_slice_value(&t));
+        assert_eq!(Some(0), argmin(&t));
+        assert_eq!(Some(4), argmax(&t));
+    }
+
+    #[test]
+    fn nan_propagates_check() {
+        let t = [3.0, 1.0, f64::NAN, 0.5, f64::NAN];
+        assert_eq!(Some(2), argmin(&t));
+        assert_eq!(Some(2), argmax(&t));
+        assert!(mean_slice(&t).unwrap().is_nan());
+        assert!(max_slice_value(&[f64::NAN, 1.0]).unwrap().is_nan());
+    }
+
+    #[test]
+    fn nan_skipped_check() {
+        let t = [f64::NAN, 3.0, 1.0, f64::NAN, 0.5, 3.0];
+        assert_eq!(Some(7.5), nan_sum_slice(&t));
+        assert_eq!(Some(1.875), nan_mean_slice(&t));
+        assert_eq!(Some(0.5), nan_min_slice_value(&t));
+        assert_eq!(Some(3.0), nan_max_slice_value(&t));
+        assert_eq!(Some(4), nan_argmin(&t));
+        assert_eq!(Some(1), nan_argmax(&t));
+
+        let all_nan = [f32::NAN; 3];
+        assert_eq!(None, nan_mean_slice(&all_nan));
+        assert_eq!(None, nan_argmax(&all_nan));
+    }
+
+    #[test]
+    fn long_f32_mean_check() {
+        // Past 2^24 elements, where an f32 count gets stuck (that gave
+        // 0.396). The tiny late steps still round, hence the slack.
+        let it =
+            std::iter::repeat_n(0.0f32, 30_000_000).chain(std::iter::repeat_n(1.0, 10_000_000));
+        let m = running_mean(it).unwrap();
+        assert!((m - 0.25).abs() < 1e-2, "{m}");
+    }
+}
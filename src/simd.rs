@@ -0,0 +1,575 @@
+//! Fast paths for `dot_slice()` and `mul_slice()` on primitive numbers.
+//!
+//! The public functions stay generic over `Num`, which has a couple of
+//! hidden methods handing back a kernel from here, or `None`. f32 and
+//! f64 override them with the kernels below, i32 and u32 do for the
+//! dot product; anything else takes the generic path. The kernels go
+//! through `std::arch`, picked at runtime by CPU feature detection.
+//!
+//! Float results can differ from a plain left-to-right loop in the last
+//! few bits, since the lanes get summed in a different order.
+//!
+//! Integer dot products wrap on overflow in the SIMD lanes, so they are
+//! only handed out without `debug_assertions`; debug builds keep the
+//! usual overflow panic. That's only a stand-in for `overflow-checks`
+//! (stable Rust can't see that one): a profile that turns
+//! `overflow-checks` on without debug assertions gets a wrapped i32/u32
+//! dot product here instead of a panic.
+
+use std::slice;
+
+/// Integer kernels wrap, so they're only used where `+` would too
+/// (going by `debug_assertions`, see above).
+pub(crate) const INT_LANES: bool = !cfg!(debug_assertions);
+
+/// What `Num::dot_kernel()` hands back. Stops at the shorter slice.
+pub(crate) type DotKernel<T> = fn(&[T], &[T]) -> T;
+
+/// What `Num::mul_kernel()` hands back: `a`, `b`, then `out`. Stops at
+/// the shortest of the three.
+pub(crate) type MulKernel<T> = fn(&[T], &[T], &mut [T]);
+
+// The `Num` hooks are safe and callable from anywhere, so everything
+// below cuts its inputs down to a common length before the `arch`
+// kernels (which trust `a.len()`) get to them.
+
+fn pair<'a, T>(a: &'a [T], b: &'a [T]) -> (&'a [T], &'a [T]) {
+    let n = a.len().min(b.len());
+    (&a[..n], &b[..n])
+}
+
+fn triple<'a, 'o, T>(a: &'a [T], b: &'a [T], out: &'o mut [T]) -> (&'a [T], &'a [T], &'o mut [T]) {
+    let n = a.len().min(b.len()).min(out.len());
+    (&a[..n], &b[..n], &mut out[..n])
+}
+
+pub(crate) fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+    let (a, b) = pair(a, b);
+    arch::dot_f32(a, b)
+}
+
+pub(crate) fn dot_f64(a: &[f64], b: &[f64]) -> f64 {
+    let (a, b) = pair(a, b);
+    arch::dot_f64(a, b)
+}
+
+/// Wrapping u32 dot product.
+pub(crate) fn dot_u32(a: &[u32], b: &[u32]) -> u32 {
+    let (a, b) = pair(a, b);
+    arch::dot_u32(a, b)
+}
+
+/// Wrapping i32 dot product, done as u32 (same bits either way).
+pub(crate) fn dot_i32(a: &[i32], b: &[i32]) -> i32 {
+    dot_u32(a.as_u32(), b.as_u32()) as i32
+}
+
+pub(crate) fn mul_f32(a: &[f32], b: &[f32], out: &mut [f32]) {
+    let (a, b, out) = triple(a, b, out);
+    arch::mul_f32(a, b, out)
+}
+
+pub(crate) fn mul_f64(a: &[f64], b: &[f64], out: &mut [f64]) {
+    let (a, b, out) = triple(a, b, out);
+    arch::mul_f64(a, b, out)
+}
+
+trait AsU32 {
+    fn as_u32(&self) -> &[u32];
+}
+
+impl AsU32 for [i32] {
+    fn as_u32(&self) -> &[u32] {
+        // SAFETY: i32 and u32 share size and alignment, and every bit
+        // pattern is valid for both. Wrapping mul/add agree on the bits.
+        unsafe { slice::from_raw_parts(self.as_ptr() as *const u32, self.len()) }
+    }
+}
+
+/// Plain loops for whatever the vector kernels leave over.
+mod scalar {
+    pub(super) fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).fold(0.0, |acc, (x, y)| acc + x * y)
+    }
+
+    pub(super) fn dot_f64(a: &[f64], b: &[f64]) -> f64 {
+        a.iter().zip(b).fold(0.0, |acc, (x, y)| acc + x * y)
+    }
+
+    pub(super) fn dot_u32(a: &[u32], b: &[u32]) -> u32 {
+        a.iter()
+            .zip(b)
+            .fold(0, |acc: u32, (x, y)| acc.wrapping_add(x.wrapping_mul(*y)))
+    }
+
+    pub(super) fn mul_f32(a: &[f32], b: &[f32], out: &mut [f32]) {
+        for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+            *o = x * y;
+        }
+    }
+
+    pub(super) fn mul_f64(a: &[f64], b: &[f64], out: &mut [f64]) {
+        for ((o, x), y) in out.iter_mut().zip(a).zip(b) {
+            *o = x * y;
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod arch {
+    use super::scalar;
+    use std::arch::x86_64::*;
+
+    pub(super) fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+        // SAFETY: each kernel only runs once its feature is detected.
+        unsafe {
+            if is_x86_feature_detected!("avx512f") {
+                dot_f32_avx512(a, b)
+            } else if is_x86_feature_detected!("avx") {
+                dot_f32_avx(a, b)
+            } else {
+                dot_f32_sse2(a, b)
+            }
+        }
+    }
+
+    pub(super) fn dot_f64(a: &[f64], b: &[f64]) -> f64 {
+        // SAFETY: each kernel only runs once its feature is detected.
+        unsafe {
+            if is_x86_feature_detected!("avx512f") {
+                dot_f64_avx512(a, b)
+            } else if is_x86_feature_detected!("avx") {
+                dot_f64_avx(a, b)
+            } else {
+                dot_f64_sse2(a, b)
+            }
+        }
+    }
+
+    pub(super) fn dot_u32(a: &[u32], b: &[u32]) -> u32 {
+        if is_x86_feature_detected!("avx2") {
+            // SAFETY: avx2 was just detected.
+            unsafe { dot_u32_avx2(a, b) }
+        } else {
+            scalar::dot_u32(a, b)
+        }
+    }
+
+    pub(super) fn mul_f32(a: &[f32], b: &[f32], out: &mut [f32]) {
+        // SAFETY: each kernel only runs once its feature is detected.
+        unsafe {
+            if is_x86_feature_detected!("avx512f") {
+                mul_f32_avx512(a, b, out)
+            } else if is_x86_feature_detected!("avx") {
+                mul_f32_avx(a, b, out)
+            } else {
+                mul_f32_sse2(a, b, out)
+            }
+        }
+    }
+
+    pub(super) fn mul_f64(a: &[f64], b: &[f64], out: &mut [f64]) {
+        // SAFETY: each kernel only runs once its feature is detected.
+        unsafe {
+            if is_x86_feature_detected!("avx512f") {
+                mul_f64_avx512(a, b, out)
+            } else if is_x86_feature_detected!("avx") {
+                mul_f64_avx(a, b, out)
+            } else {
+                mul_f64_sse2(a, b, out)
+            }
+        }
+    }
+
+    /// Stamps out a dot kernel: `$lanes` wide loads, multiply, add into
+    /// one accumulator, then fold the lanes and finish the tail by hand.
+    macro_rules! dot_kernel {
+        (
+            $name:ident, $feature:literal, $t:ty, $lanes:expr,
+            $zero:ident, $load:ident, $mul:ident, $add:ident, $store:ident,
+            $tail:path
+        ) => {
+            #[target_feature(enable = $feature)]
+            unsafe fn $name(a: &[$t], b: &[$t]) -> $t {
+                let n = a.len() - a.len() % $lanes;
+                let mut acc = $zero();
+                let mut i = 0;
+                while i < n {
+                    let x = $load(a.as_ptr().add(i) as *const _);
+                    let y = $load(b.as_ptr().add(i) as *const _);
+                    acc = $add(acc, $mul(x, y));
+                    i += $lanes;
+                }
+                let mut lanes = [<$t>::default(); $lanes];
+                $store(lanes.as_mut_ptr() as *mut _, acc);
+                let mut sum = <$t>::default();
+                for l in lanes {
+                    sum = sum.wrapping_or_plain_add(l);
+                }
+                sum.wrapping_or_plain_add($tail(&a[n..], &b[n..]))
+            }
+        };
+    }
+
+    /// Stamps out an element-wise multiply kernel.
+    macro_rules! mul_kernel {
+        (
+            $name:ident, $feature:literal, $t:ty, $lanes:expr,
+            $load:ident, $mul:ident, $store:ident, $tail:path
+        ) => {
+            #[target_feature(enable = $feature)]
+            unsafe fn $name(a: &[$t], b: &[$t], out: &mut [$t]) {
+                let n = a.len() - a.len() % $lanes;
+                let mut i = 0;
+                while i < n {
+                    let x = $load(a.as_ptr().add(i) as *const _);
+                    let y = $load(b.as_ptr().add(i) as *const _);
+                    $store(out.as_mut_ptr().add(i) as *mut _, $mul(x, y));
+                    i += $lanes;
+                }
+                $tail(&a[n..], &b[n..], &mut out[n..]);
+            }
+        };
+    }
+
+    /// Floats add plainly, integers wrap like their SIMD lanes do.
+    trait LaneAdd {
+        fn wrapping_or_plain_add(self, o: Self) -> Self;
+    }
+
+    impl LaneAdd for f32 {
+        fn wrapping_or_plain_add(self, o: f32) -> f32 {
+            self + o
+        }
+    }
+
+    impl LaneAdd for f64 {
+        fn wrapping_or_plain_add(self, o: f64) -> f64 {
+            self + o
+        }
+    }
+
+    impl LaneAdd for u32 {
+        fn wrapping_or_plain_add(self, o: u32) -> u32 {
+            self.wrapping_add(o)
+        }
+    }
+
+    dot_kernel!(
+        dot_f32_avx512,
+        "avx512f",
+        f32,
+        16,
+        _mm512_setzero_ps,
+        _mm512_loadu_ps,
+        _mm512_mul_ps,
+        _mm512_add_ps,
+        _mm512_storeu_ps,
+        scalar::dot_f32
+    );
+    dot_kernel!(
+        dot_f32_avx,
+        "avx",
+        f32,
+        8,
+        _mm256_setzero_ps,
+        _mm256_loadu_ps,
+        _mm256_mul_ps,
+        _mm256_add_ps,
+        _mm256_storeu_ps,
+        scalar::dot_f32
+    );
+    dot_kernel!(
+        dot_f32_sse2,
+        "sse2",
+        f32,
+        4,
+        _mm_setzero_ps,
+        _mm_loadu_ps,
+        _mm_mul_ps,
+        _mm_add_ps,
+        _mm_storeu_ps,
+        scalar::dot_f32
+    );
+    dot_kernel!(
+        dot_f64_avx512,
+        "avx512f",
+        f64,
+        8,
+        _mm512_setzero_pd,
+        _mm512_loadu_pd,
+        _mm512_mul_pd,
+        _mm512_add_pd,
+        _mm512_storeu_pd,
+        scalar::dot_f64
+    );
+    dot_kernel!(
+        dot_f64_avx,
+        "avx",
+        f64,
+        4,
+        _mm256_setzero_pd,
+        _mm256_loadu_pd,
+        _mm256_mul_pd,
+        _mm256_add_pd,
+        _mm256_storeu_pd,
+        scalar::dot_f64
+    );
+    dot_kernel!(
+        dot_f64_sse2,
+        "sse2",
+        f64,
+        2,
+        _mm_setzero_pd,
+        _mm_loadu_pd,
+        _mm_mul_pd,
+        _mm_add_pd,
+        _mm_storeu_pd,
+        scalar::dot_f64
+    );
+    dot_kernel!(
+        dot_u32_avx2,
+        "avx2",
+        u32,
+        8,
+        _mm256_setzero_si256,
+        _mm256_loadu_si256,
+        _mm256_mullo_epi32,
+        _mm256_add_epi32,
+        _mm256_storeu_si256,
+        scalar::dot_u32
+    );
+
+    mul_kernel!(
+        mul_f32_avx512,
+        "avx512f",
+        f32,
+        16,
+        _mm512_loadu_ps,
+        _mm512_mul_ps,
+        _mm512_storeu_ps,
+        scalar::mul_f32
+    );
+    mul_kernel!(
+        mul_f32_avx,
+        "avx",
+        f32,
+        8,
+        _mm256_loadu_ps,
+        _mm256_mul_ps,
+        _mm256_storeu_ps,
+        scalar::mul_f32
+    );
+    mul_kernel!(
+        mul_f32_sse2,
+        "sse2",
+        f32,
+        4,
+        _mm_loadu_ps,
+        _mm_mul_ps,
+        _mm_storeu_ps,
+        scalar::mul_f32
+    );
+    mul_kernel!(
+        mul_f64_avx512,
+        "avx512f",
+        f64,
+        8,
+        _mm512_loadu_pd,
+        _mm512_mul_pd,
+        _mm512_storeu_pd,
+        scalar::mul_f64
+    );
+    mul_kernel!(
+        mul_f64_avx,
+        "avx",
+        f64,
+        4,
+        _mm256_loadu_pd,
+        _mm256_mul_pd,
+        _mm256_storeu_pd,
+        scalar::mul_f64
+    );
+    mul_kernel!(
+        mul_f64_sse2,
+        "sse2",
+        f64,
+        2,
+        _mm_loadu_pd,
+        _mm_mul_pd,
+        _mm_storeu_pd,
+        scalar::mul_f64
+    );
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arch {
+    use super::scalar;
+    use std::arch::aarch64::*;
+
+    // NEON is part of the aarch64 baseline, so there's nothing to
+    // detect; the chunking just mirrors the x86 kernels.
+
+    pub(super) fn dot_f32(a: &[f32], b: &[f32]) -> f32 {
+        let n = a.len() - a.len() % 4;
+        // SAFETY: neon is always there on aarch64 and we stay below `n`.
+        unsafe {
+            let mut acc = vdupq_n_f32(0.0);
+            for i in (0..n).step_by(4) {
+                let x = vld1q_f32(a.as_ptr().add(i));
+                let y = vld1q_f32(b.as_ptr().add(i));
+                acc = vaddq_f32(acc, vmulq_f32(x, y));
+            }
+            vaddvq_f32(acc) + scalar::dot_f32(&a[n..], &b[n..])
+        }
+    }
+
+    pub(super) fn dot_f64(a: &[f64], b: &[f64]) -> f64 {
+        let n = a.len() - a.len() % 2;
+        // SAFETY: neon is always there on aarch64 and we stay below `n`.
+        unsafe {
+            let mut acc = vdupq_n_f64(0.0);
+            for i in (0..n).step_by(2) {
+                let x = vld1q_f64(a.as_ptr().add(i));
+                let y = vld1q_f64(b.as_ptr().add(i));
+                acc = vaddq_f64(acc, vmulq_f64(x, y));
+            }
+            vaddvq_f64(acc) + scalar::dot_f64(&a[n..], &b[n..])
+        }
+    }
+
+    pub(super) fn dot_u32(a: &[u32], b: &[u32]) -> u32 {
+        let n = a.len() - a.len() % 4;
+        // SAFETY: neon is always there on aarch64 and we stay below `n`.
+        unsafe {
+            let mut acc = vdupq_n_u32(0);
+            for i in (0..n).step_by(4) {
+                let x = vld1q_u32(a.as_ptr().add(i));
+                let y = vld1q_u32(b.as_ptr().add(i));
+                acc = vmlaq_u32(acc, x, y);
+            }
+            vaddvq_u32(acc).wrapping_add(scalar::dot_u32(&a[n..], &b[n..]))
+        }
+    }
+
+    pub(super) fn mul_f32(a: &[f32], b: &[f32], out: &mut [f32]) {
+        let n = a.len() - a.len() % 4;
+        // SAFETY: neon is always there on aarch64 and we stay below `n`.
+        unsafe {
+            for i in (0..n).step_by(4) {
+                let x = vld1q_f32(a.as_ptr().add(i));
+                let y = vld1q_f32(b.as_ptr().add(i));
+                vst1q_f32(out.as_mut_ptr().add(i), vmulq_f32(x, y));
+            }
+        }
+        scalar::mul_f32(&a[n..], &b[n..], &mut out[n..]);
+    }
+
+    pub(super) fn mul_f64(a: &[f64], b: &[f64], out: &mut [f64]) {
+        let n = a.len() - a.len() % 2;
+        // SAFETY: neon is always there on aarch64 and we stay below `n`.
+        unsafe {
+            for i in (0..n).step_by(2) {
+                let x = vld1q_f64(a.as_ptr().add(i));
+                let y = vld1q_f64(b.as_ptr().add(i));
+                vst1q_f64(out.as_mut_ptr().add(i), vmulq_f64(x, y));
+            }
+        }
+        scalar::mul_f64(&a[n..], &b[n..], &mut out[n..]);
+    }
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod arch {
+    pub(super) use super::scalar::*;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Num;
+
+    fn ramp(n: usize, k: f64) -> Vec<f64> {
+        (0..n).map(|i| (i as f64 * k).sin()).collect()
+    }
+
+    #[test]
+    fn dispatch_check() {
+        assert!(f32::dot_kernel().is_some() && f64::mul_kernel().is_some());
+        assert_eq!(INT_LANES, i32::dot_kernel().is_some());
+        assert_eq!(INT_LANES, u32::dot_kernel().is_some());
+        assert!(u8::dot_kernel().is_none() && i64::mul_kernel().is_none());
+        assert!(std::num::Wrapping::<u32>::dot_kernel().is_none());
+        assert_eq!(-5, dot_i32(&[1, -2, 3], &[-4, 5, 3]));
+    }
+
+    #[test]
+    fn mismatched_len_check() {
+        // The hooks are reachable from safe code, so lengths that
+        // don't line up have to be fine too.
+        let dot = f64::dot_kernel().unwrap();
+        assert_eq!(0.0, dot(&[1.0; 64], &[]));
+        assert_eq!(40.0, dot(&[2.0; 64], &[1.0; 20]));
+        assert_eq!(8.0, dot_f32(&[1.0; 8], &[1.0; 33]));
+        assert_eq!(3, dot_i32(&[1; 3], &[1; 40]));
+
+        let mul = f32::mul_kernel().unwrap();
+        mul(&[1.0; 32], &[1.0; 32], &mut []);
+        let mut out = [0.0f32; 40];
+        mul(&[2.0; 32], &[3.0; 17], &mut out);
+        assert!(out[..17].iter().all(|&x| x == 6.0) && out[17..].iter().all(|&x| x == 0.0));
+        let mut out = [0.0; 5];
+        mul_f64(&[2.0; 3], &[3.0; 64], &mut out);
+        assert_eq!([6.0, 6.0, 6.0, 0.0, 0.0], out);
+    }
+
+    #[test]
+    fn dot_f64_check() {
+        for n in 0..70 {
+            let (a, b) = (ramp(n, 0.3), ramp(n, 0.7));
+            let e = scalar::dot_f64(&a, &b);
+            let q = dot_f64(&a, &b);
+            assert!((e - q).abs() < 1e-12, "n = {n}: {e} vs {q}");
+        }
+    }
+
+    #[test]
+    fn dot_f32_check() {
+        for n in 0..70 {
+            let a: Vec<f32> = ramp(n, 0.3).iter().map(|&x| x as f32).collect();
+            let b: Vec<f32> = ramp(n, 0.7).iter().map(|&x| x as f32).collect();
+            let e = scalar::dot_f32(&a, &b);
+            let q = dot_f32(&a, &b);
+            assert!((e - q).abs() < 1e-4, "n = {n}: {e} vs {q}");
+        }
+    }
+
+    #[test]
+    fn dot_u32_check() {
+        for n in 0..70 {
+            let a: Vec<u32> = (0..n).map(|i: u32| i.wrapping_mul(0x1234_5678)).collect();
+            let b: Vec<u32> = (0..n).map(|i| i + 7).collect();
+            assert_eq!(scalar::dot_u32(&a, &b), dot_u32(&a, &b));
+        }
+    }
+
+    #[test]
+    fn mul_check() {
+        for n in 0..40 {
+            let (a, b) = (ramp(n, 0.3), ramp(n, 0.7));
+            let mut e = vec![0.0; n];
+            let mut q = vec![0.0; n];
+            scalar::mul_f64(&a, &b, &mut e);
+            mul_f64(&a, &b, &mut q);
+            assert_eq!(e, q);
+
+            let a: Vec<f32> = a.iter().map(|&x| x as f32).collect();
+            let b: Vec<f32> = b.iter().map(|&x| x as f32).collect();
+            let mut e = vec![0.0f32; n];
+            let mut q = vec![0.0f32; n];
+            scalar::mul_f32(&a, &b, &mut e);
+            mul_f32(&a, &b, &mut q);
+            assert_eq!(e, q);
+        }
+    }
+}
@@ -0,0 +1,435 @@
+//! Descriptive statistics over float slices.
+//!
+//! Everything is worked out in a single pass with Welford's online
+//! algorithm (and its extensions for the higher moments), which stays
+//! stable where the textbook sum-of-squares formulas fall apart.
+//!
+//! Functions taking two slices follow `dot_slice()`: sizes don't have
+//! to match, only the shortest slice counts. Run them through
+//! `LengthPolicy::Strict` first if that's not what you want.
+//!
+//! Not enough data (or no spread at all, for correlations) gives `None`.
+
+use std::cmp::Ordering;
+
+use crate::num::Float;
+
+/// # Estimator
+/// Which flavour of statistic you want.
+///
+/// `Population` treats the slice as everything
+/// there is (divide by n).
+///
+/// `Sample` treats it as a sample of something
+/// bigger and corrects for the bias (divide by
+/// n - 1 for variance, and the usual adjustments
+/// for skewness and kurtosis).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Estimator {
+    #[default]
+    Population,
+    Sample,
+}
+
+/// # RunningStats
+/// Welford style accumulator. Feed it values
+/// one at a time (or a slice at a time) and ask
+/// for the mean, variance, skewness or kurtosis
+/// whenever you like.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::stats::{Estimator, RunningStats};
+///
+/// let mut s = RunningStats::new();
+/// s.extend_from_slice(&[2.0, 4.0, 4.0, 4.0]);
+/// s.push(5.0);
+/// s.extend_from_slice(&[5.0, 7.0, 9.0]);
+/// assert_eq!(Some(5.0), s.mean());
+/// let v: f64 = s.variance(Estimator::Population).unwrap();
+/// assert!((v - 4.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RunningStats<T> {
+    n: usize,
+    mean: T,
+    m2: T,
+    m3: T,
+    m4: T,
+}
+
+impl<T: Float> RunningStats<T> {
+    pub fn new() -> Self {
+        RunningStats {
+            n: 0,
+            mean: T::ZERO,
+            m2: T::ZERO,
+            m3: T::ZERO,
+            m4: T::ZERO,
+        }
+    }
+
+    /// Adds one value.
+    pub fn push(&mut self, x: T) {
+        let n1 = T::from_f64(self.n as f64);
+        self.n += 1;
+        let n = T::from_f64(self.n as f64);
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * n1;
+        let three = T::from_f64(3.0);
+        let six = T::from_f64(6.0);
+
+        self.mean = self.mean + delta_n;
+        self.m4 =
+            self.m4 + term * delta_n2 * (n * n - three * n + three) + six * delta_n2 * self.m2
+                - T::from_f64(4.0) * delta_n * self.m3;
+        self.m3 = self.m3 + term * delta_n * (n - T::from_f64(2.0)) - three * delta_n * self.m2;
+        self.m2 = self.m2 + term;
+    }
+
+    /// Adds every value in `a`.
+    pub fn extend_from_slice(&mut self, a: &[T]) {
+        for &x in a {
+            self.push(x);
+        }
+    }
+
+    /// How many values have gone in.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    pub fn mean(&self) -> Option<T> {
+        if self.n == 0 {
+            return None;
+        }
+        Some(self.mean)
+    }
+
+    /// Needs one value for `Population`, two for `Sample`.
+    pub fn variance(&self, est: Estimator) -> Option<T> {
+        let n = self.n as f64;
+        match est {
+            Estimator::Population if self.n >= 1 => Some(self.m2 / T::from_f64(n)),
+            Estimator::Sample if self.n >= 2 => Some(self.m2 / T::from_f64(n - 1.0)),
+            _ => None,
+        }
+    }
+
+    pub fn std_dev(&self, est: Estimator) -> Option<T> {
+        self.variance(est).map(|v| T::from_f64(v.to_f64().sqrt()))
+    }
+
+    /// Needs one value for `Population`, three for `Sample`.
+    /// No spread at all gives NaN, like 0 / 0.
+    pub fn skewness(&self, est: Estimator) -> Option<T> {
+        let n = self.n as f64;
+        let (m2, m3) = (self.m2.to_f64(), self.m3.to_f64());
+        let g1 = n.sqrt() * m3 / m2.powf(1.5);
+        match est {
+            Estimator::Population if self.n >= 1 => Some(T::from_f64(g1)),
+            Estimator::Sample if self.n >= 3 => {
+                Some(T::from_f64(g1 * (n * (n - 1.0)).sqrt() / (n - 2.0)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Excess kurtosis, so a normal distribution comes out near 0.
+    ///
+    /// Needs one value for `Population`, four for `Sample`.
+    /// No spread at all gives NaN, like 0 / 0.
+    pub fn kurtosis(&self, est: Estimator) -> Option<T> {
+        let n = self.n as f64;
+        let (m2, m4) = (self.m2.to_f64(), self.m4.to_f64());
+        let g2 = n * m4 / (m2 * m2) - 3.0;
+        match est {
+            Estimator::Population if self.n >= 1 => Some(T::from_f64(g2)),
+            Estimator::Sample if self.n >= 4 => Some(T::from_f64(
+                ((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0)),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Single pass co-moment of a couple of slices: (n, mean a, mean b,
+/// Σ da·db, Σ da², Σ db²).
+fn co_moments<T: Float>(a: &[T], b: &[T]) -> (usize, T, T, T, T, T) {
+    let (mut ma, mut mb) = (T::ZERO, T::ZERO);
+    let (mut cab, mut caa, mut cbb) = (T::ZERO, T::ZERO, T::ZERO);
+    let mut n = 0;
+    for (&x, &y) in a.iter().zip(b) {
+        n += 1;
+        let k = T::from_f64(n as f64);
+        let dx = x - ma;
+        let dy = y - mb;
+        ma = ma + dx / k;
+        mb = mb + dy / k;
+        cab = cab + dx * (y - mb);
+        caa = caa + dx * (x - ma);
+        cbb = cbb + dy * (y - mb);
+    }
+    (n, ma, mb, cab, caa, cbb)
+}
+
+/// # variance()
+/// Takes a reference to a float slice and
+/// an `Estimator`.
+///
+/// Returns the variance, or `None` if there's
+/// not enough data (one value for `Population`,
+/// two for `Sample`).
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::stats::{variance, Estimator};
+///
+/// let t = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+/// let v: f64 = variance(&t, Estimator::Population).unwrap();
+/// assert!((v - 4.0).abs() < 1e-12);
+/// let v: f64 = variance(&t, Estimator::Sample).unwrap();
+/// assert!((v - 32.0 / 7.0).abs() < 1e-12);
+/// ```
+pub fn variance<T: Float>(a: &[T], est: Estimator) -> Option<T> {
+    let mut s = RunningStats::new();
+    s.extend_from_slice(a);
+    s.variance(est)
+}
+
+/// # std_dev()
+/// Takes a reference to a float slice and
+/// an `Estimator`.
+///
+/// Returns the standard deviation (square root
+/// of `variance()`), or `None` if there's not
+/// enough data.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::stats::{std_dev, Estimator};
+///
+/// let t = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+/// let s: f64 = std_dev(&t, Estimator::Population).unwrap();
+/// assert!((s - 2.0).abs() < 1e-12);
+/// ```
+pub fn std_dev<T: Float>(a: &[T], est: Estimator) -> Option<T> {
+    let mut s = RunningStats::new();
+    s.extend_from_slice(a);
+    s.std_dev(est)
+}
+
+/// # covariance()
+/// Takes a reference to a couple of float
+/// slices and an `Estimator`.
+///
+/// Returns their covariance, or `None` if
+/// there's not enough data. Only calculates
+/// for the shortest slice.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::stats::{covariance, Estimator};
+///
+/// let t = [1.0, 2.0, 3.0, 4.0];
+/// let u = [2.0, 4.0, 6.0, 8.0, 100.0];
+/// assert_eq!(Some(2.5), covariance(&t, &u, Estimator::Population));
+/// ```
+pub fn covariance<T: Float>(a: &[T], b: &[T], est: Estimator) -> Option<T> {
+    let (n, _, _, cab, _, _) = co_moments(a, b);
+    match est {
+        Estimator::Population if n >= 1 => Some(cab / T::from_f64(n as f64)),
+        Estimator::Sample if n >= 2 => Some(cab / T::from_f64(n as f64 - 1.0)),
+        _ => None,
+    }
+}
+
+/// # pearson_correlation()
+/// Takes a reference to a couple of float slices.
+///
+/// Returns Pearson's r, somewhere in -1..=1, or
+/// `None` with fewer than two pairs or if either
+/// slice doesn't vary at all. Only calculates
+/// for the shortest slice.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::stats::pearson_correlation;
+///
+/// let t = [1.0, 2.0, 3.0, 4.0];
+/// let u = [8.0, 6.0, 4.0, 2.0];
+/// let r: f64 = pearson_correlation(&t, &u).unwrap();
+/// assert!((r + 1.0).abs() < 1e-12);
+/// ```
+pub fn pearson_correlation<T: Float>(a: &[T], b: &[T]) -> Option<T> {
+    let (n, _, _, cab, caa, cbb) = co_moments(a, b);
+    if n < 2 || caa == T::ZERO || cbb == T::ZERO {
+        return None;
+    }
+    let r = cab.to_f64() / (caa.to_f64().sqrt() * cbb.to_f64().sqrt());
+    Some(T::from_f64(r.clamp(-1.0, 1.0)))
+}
+
+/// # spearman_correlation()
+/// Takes a reference to a couple of float slices.
+///
+/// Returns Spearman's rank correlation: Pearson's r
+/// of the ranks, with ties getting the average of
+/// their ranks. `None` with fewer than two pairs,
+/// no variation, or any NaN. Only calculates for
+/// the shortest slice.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::stats::spearman_correlation;
+///
+/// let t = [1.0, 2.0, 3.0, 4.0];
+/// let u = [1.0, 8.0, 27.0, 64.0];
+/// let r: f64 = spearman_correlation(&t, &u).unwrap();
+/// assert!((r - 1.0).abs() < 1e-12);
+/// ```
+pub fn spearman_correlation<T: Float>(a: &[T], b: &[T]) -> Option<T> {
+    let len = a.len().min(b.len());
+    let ra = ranks(&a[..len])?;
+    let rb = ranks(&b[..len])?;
+    pearson_correlation(&ra, &rb)
+}
+
+/// 1-based ranks, ties averaged. `None` if anything won't compare.
+fn ranks<T: Float>(a: &[T]) -> Option<Vec<T>> {
+    if a.iter().any(|x| x.is_nan()) {
+        return None;
+    }
+    let mut idx: Vec<usize> = (0..a.len()).collect();
+    idx.sort_by(|&i, &j| a[i].partial_cmp(&a[j]).unwrap_or(Ordering::Equal));
+
+    let mut r = vec![T::ZERO; a.len()];
+    let mut i = 0;
+    while i < idx.len() {
+        let mut j = i;
+        while j + 1 < idx.len() && a[idx[j + 1]] == a[idx[i]] {
+            j += 1;
+        }
+        let avg = T::from_f64((i + j) as f64 / 2.0 + 1.0);
+        for &k in &idx[i..=j] {
+            r[k] = avg;
+        }
+        i = j + 1;
+    }
+    Some(r)
+}
+
+/// # skewness()
+/// Takes a reference to a float slice and
+/// an `Estimator`.
+///
+/// Returns how lopsided the data is: positive
+/// means a longer tail on the right. `Sample`
+/// gives the adjusted Fisher-Pearson estimate
+/// and needs at least three values.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::stats::{skewness, Estimator};
+///
+/// let t = [1.0, 2.0, 3.0];
+/// assert_eq!(Some(0.0), skewness(&t, Estimator::Population));
+/// ```
+pub fn skewness<T: Float>(a: &[T], est: Estimator) -> Option<T> {
+    let mut s = RunningStats::new();
+    s.extend_from_slice(a);
+    s.skewness(est)
+}
+
+/// # kurtosis()
+/// Takes a reference to a float slice and
+/// an `Estimator`.
+///
+/// Returns the excess kurtosis (0 for a normal
+/// distribution): how heavy the tails are.
+/// `Sample` gives the bias-corrected estimate
+/// and needs at least four values.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::stats::{kurtosis, Estimator};
+///
+/// let t = [1.0, 2.0, 3.0, 4.0];
+/// assert_eq!(Some(-1.36), kurtosis(&t, Estimator::Population));
+/// ```
+pub fn kurtosis<T: Float>(a: &[T], est: Estimator) -> Option<T> {
+    let mut s = RunningStats::new();
+    s.extend_from_slice(a);
+    s.kurtosis(est)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(e: f64, q: Option<f64>) -> bool {
+        (e - q.unwrap()).abs() < 1e-9
+    }
+
+    #[test]
+    fn not_enough_data_check() {
+        let e: [f64; 0] = [];
+        assert_eq!(None, variance(&e, Estimator::Population));
+        assert_eq!(None, variance(&[1.0], Estimator::Sample));
+        assert_eq!(Some(0.0), variance(&[1.0], Estimator::Population));
+        assert_eq!(None, covariance(&[1.0], &[], Estimator::Population));
+        assert_eq!(None, pearson_correlation(&[1.0, 1.0], &[1.0, 2.0]));
+        assert_eq!(None, skewness(&[1.0, 2.0], Estimator::Sample));
+        assert_eq!(None, kurtosis(&[1.0, 2.0, 3.0], Estimator::Sample));
+        assert_eq!(None, spearman_correlation(&[1.0, f64::NAN], &[1.0, 2.0]));
+    }
+
+    #[test]
+    fn welford_stability_check() {
+        // Textbook E[x²] - E[x]² loses everything here.
+        let t: Vec<f64> = [4.0, 7.0, 13.0, 16.0].iter().map(|x| x + 1e9).collect();
+        assert!(close(30.0, variance(&t, Estimator::Sample)));
+        assert!(close(0.0, skewness(&t, Estimator::Population)));
+    }
+
+    #[test]
+    fn moments_check() {
+        // Reference values worked out by hand from the definitions.
+        let t = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+        let mut s = RunningStats::new();
+        s.extend_from_slice(&t);
+        assert_eq!(8, s.len());
+        assert!(close(4.125, s.mean()));
+        assert!(close(13.859375, variance(&t, Estimator::Population)));
+        assert!(close(3.9798600118956085, std_dev(&t, Estimator::Sample)));
+        assert!(close(
+            0.2650554122698573,
+            skewness(&t, Estimator::Population)
+        ));
+        assert!(close(0.33058218040797466, skewness(&t, Estimator::Sample)));
+        assert!(close(
+            -1.6660010752838508,
+            kurtosis(&t, Estimator::Population)
+        ));
+        assert!(close(-2.098602258096087, kurtosis(&t, Estimator::Sample)));
+    }
+
+    #[test]
+    fn correlation_check() {
+        let t = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let u = [2.0, 1.0, 4.0, 3.0, 7.0, -1.0];
+        assert!(close(3.0, covariance(&t, &u, Estimator::Sample)));
+        assert!(close(0.824163383692134, pearson_correlation(&t, &u)));
+        assert!(close(0.8, spearman_correlation(&t, &u)));
+
+        let v = [1.0, 2.0, 2.0, 3.0];
+        let w = [1.0, 3.0, 2.0, 4.0];
+        assert!(close(0.9486832980505138, spearman_correlation(&v, &w)));
+    }
+}
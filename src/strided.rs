@@ -0,0 +1,315 @@
+//! Non-contiguous input: every Nth element, a matrix column, a slice
+//! read backwards.
+//!
+//! `StridedSlice` borrows a slice and walks it `stride` elements at a
+//! time. `SliceInput` is what `dot_slice()` takes (and, through
+//! `Broadcast`, the element-wise ops), so a `StridedSlice` goes
+//! anywhere a plain slice (or array, or Vec) does. Contiguous input
+//! still gets the SIMD fast paths; strided input takes the plain loop.
+
+use std::ops::Index;
+
+use crate::matrix::ShapeError;
+
+/// # SliceInput
+/// Anything the slice functions can read from:
+/// a length and an element at each index.
+///
+/// Implemented for slices, arrays, Vecs,
+/// `StridedSlice` and references to any of them.
+pub trait SliceInput {
+    type Item: Copy;
+
+    /// How many elements there are.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Element `i`. Panics if `i` is out of range.
+    fn at(&self, i: usize) -> Self::Item;
+
+    /// The elements as one plain slice, if that's how
+    /// they sit in memory. Turns on the fast paths.
+    fn as_contiguous(&self) -> Option<&[Self::Item]> {
+        None
+    }
+}
+
+impl<T: Copy> SliceInput for [T] {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn at(&self, i: usize) -> T {
+        self[i]
+    }
+
+    fn as_contiguous(&self) -> Option<&[T]> {
+        Some(self)
+    }
+}
+
+impl<T: Copy, const N: usize> SliceInput for [T; N] {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        N
+    }
+
+    fn at(&self, i: usize) -> T {
+        self[i]
+    }
+
+    fn as_contiguous(&self) -> Option<&[T]> {
+        Some(self)
+    }
+}
+
+impl<T: Copy> SliceInput for Vec<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn at(&self, i: usize) -> T {
+        self[i]
+    }
+
+    fn as_contiguous(&self) -> Option<&[T]> {
+        Some(self)
+    }
+}
+
+impl<S: SliceInput + ?Sized> SliceInput for &S {
+    type Item = S::Item;
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn at(&self, i: usize) -> S::Item {
+        (**self).at(i)
+    }
+
+    fn as_contiguous(&self) -> Option<&[S::Item]> {
+        (**self).as_contiguous()
+    }
+}
+
+/// # StridedSlice
+/// A borrowed view of `len` elements spaced
+/// `stride` apart in a slice.
+///
+/// A negative stride walks backwards, starting
+/// from the far end the way BLAS does: element
+/// `i` is `data[(len - 1 - i) * |stride|]`. A
+/// zero stride repeats `data[0]`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::{dot_slice, StridedSlice};
+///
+/// let data = [1, 2, 3, 4, 5, 6];
+/// let odd = StridedSlice::new(&data, 3, 2).unwrap();
+/// assert_eq!(vec![1, 3, 5], odd.to_vec());
+/// let back = StridedSlice::new(&data, 6, -1).unwrap();
+/// assert_eq!(6, back[0]);
+/// assert_eq!(1 * 6 + 3 * 5 + 5 * 4, dot_slice(&odd, &back));
+/// ```
+#[derive(Debug)]
+pub struct StridedSlice<'a, T> {
+    data: &'a [T],
+    len: usize,
+    stride: isize,
+}
+
+impl<'a, T> StridedSlice<'a, T> {
+    /// `len` elements `stride` apart. `data` has to be
+    /// long enough to reach the last one, that's
+    /// `(len - 1) * |stride| + 1` elements.
+    pub fn new(data: &'a [T], len: usize, stride: isize) -> Result<Self, ShapeError> {
+        let needed = match len {
+            0 => Some(0),
+            _ => (len - 1)
+                .checked_mul(stride.unsigned_abs())
+                .and_then(|n| n.checked_add(1)),
+        };
+        match needed {
+            Some(n) if n <= data.len() => Ok(StridedSlice { data, len, stride }),
+            n => Err(ShapeError::BufferSize {
+                needed: n.unwrap_or(usize::MAX),
+                got: data.len(),
+            }),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn stride(&self) -> isize {
+        self.stride
+    }
+
+    fn offset(&self, i: usize) -> usize {
+        let step = self.stride.unsigned_abs();
+        if self.stride >= 0 {
+            i * step
+        } else {
+            (self.len - 1 - i) * step
+        }
+    }
+
+    pub fn get(&self, i: usize) -> Option<&'a T> {
+        if i < self.len {
+            Some(&self.data[self.offset(i)])
+        } else {
+            None
+        }
+    }
+
+    /// The elements in order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a
+    where
+        T: 'a,
+    {
+        let s = *self;
+        (0..s.len).map(move |i| &s.data[s.offset(i)])
+    }
+
+    /// Same elements, back to front. No copying.
+    pub fn rev(&self) -> StridedSlice<'a, T> {
+        // With two or more elements the stride fits in the
+        // buffer, so it can't be isize::MIN.
+        let stride = if self.len > 1 {
+            -self.stride
+        } else {
+            self.stride
+        };
+        StridedSlice { stride, ..*self }
+    }
+
+    /// A plain slice over the same elements, if the
+    /// stride is one (or there's at most one element).
+    pub fn as_slice(&self) -> Option<&'a [T]> {
+        if self.stride == 1 || self.len <= 1 {
+            Some(&self.data[..self.len])
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Clone> StridedSlice<'_, T> {
+    /// Copies the elements out into a Vec.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<'a, T> From<&'a [T]> for StridedSlice<'a, T> {
+    fn from(data: &'a [T]) -> Self {
+        StridedSlice {
+            data,
+            len: data.len(),
+            stride: 1,
+        }
+    }
+}
+
+// Derived Clone/Copy would want `T: Copy`; a view is copyable regardless.
+impl<T> Clone for StridedSlice<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for StridedSlice<'_, T> {}
+
+/// Equal when the elements are, wherever they sit.
+impl<T: PartialEq> PartialEq for StridedSlice<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for StridedSlice<'_, T> {}
+
+impl<T> Index<usize> for StridedSlice<'_, T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        self.get(i)
+            .unwrap_or_else(|| panic!("index {i} out of range for a strided slice of {}", self.len))
+    }
+}
+
+impl<T: Copy> SliceInput for StridedSlice<'_, T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn at(&self, i: usize) -> T {
+        self[i]
+    }
+
+    fn as_contiguous(&self) -> Option<&[T]> {
+        self.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constructor_check() {
+        let data = [0, 1, 2, 3, 4, 5, 6];
+        assert!(StridedSlice::new(&data, 4, 2).is_ok());
+        assert_eq!(
+            Err(ShapeError::BufferSize { needed: 10, got: 7 }),
+            StridedSlice::new(&data, 4, -3)
+        );
+        assert!(StridedSlice::new(&data, 0, 100).unwrap().is_empty());
+        let z = StridedSlice::new(&data[3..], 4, 0).unwrap();
+        assert_eq!(vec![3, 3, 3, 3], z.to_vec());
+        assert!(StridedSlice::new(&data, usize::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn negative_stride_check() {
+        let data = [0, 1, 2, 3, 4, 5, 6];
+        let s = StridedSlice::new(&data, 3, -3).unwrap();
+        assert_eq!(vec![6, 3, 0], s.to_vec());
+        assert_eq!(vec![0, 3, 6], s.rev().to_vec());
+        assert_eq!(s, s.rev().rev());
+        assert_eq!(s.rev(), StridedSlice::new(&data, 3, 3).unwrap());
+        assert_eq!(None, s.get(3));
+        assert_eq!(None, s.as_slice());
+
+        let f = StridedSlice::new(&data[2..], 3, 1).unwrap();
+        assert_eq!(Some(&[2, 3, 4][..]), f.as_slice());
+        assert_eq!(vec![4, 3, 2], f.rev().to_vec());
+    }
+
+    #[test]
+    fn input_check() {
+        let v = vec![1.0, 2.0, 3.0];
+        let s = StridedSlice::from(&v[..]);
+        assert_eq!(3, SliceInput::len(&s));
+        assert_eq!(Some(&v[..]), s.as_contiguous());
+        assert_eq!(2.0, (&&v).at(1));
+        assert_eq!(None, StridedSlice::new(&v, 2, 2).unwrap().as_contiguous());
+    }
+}
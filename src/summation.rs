@@ -0,0 +1,268 @@
+//! Careful ways of adding up a float dot product.
+
+use crate::dot_slice;
+use crate::num::Float;
+
+/// Below this many products pairwise summation just loops.
+const PAIRWISE_BLOCK: usize = 32;
+
+/// # Summation
+/// How `dot_slice_with()` should add up the products.
+///
+/// `Naive` is plain `dot_slice()`, fastest and sloppiest.
+///
+/// `Kahan` carries a running compensation term
+/// (Neumaier's flavour, so big terms don't eat it).
+///
+/// `Pairwise` splits the slices in half over and over,
+/// error grows with log(n) instead of n.
+///
+/// `Widened` accumulates in f64. Great for f32;
+/// for f64 it's just a plain left-to-right loop.
+///
+/// `Dot2` is Ogita-Rump-Oishi: as good as computing in
+/// twice the precision and rounding once at the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Summation {
+    #[default]
+    Naive,
+    Kahan,
+    Pairwise,
+    Widened,
+    Dot2,
+}
+
+/// # dot_slice_with()
+/// Takes a reference to a couple of float slices
+/// and a `Summation` strategy.
+///
+/// Returns the dot product added up your way.
+/// Only calculates for the shortest slice, same
+/// as `dot_slice()`.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::{dot_slice_with, Summation};
+///
+/// let t = [1e8f32, 1.0, -1e8];
+/// let u = [1.0f32, 1.0, 1.0];
+/// assert_eq!(0.0, dot_slice_with(&t, &u, Summation::Naive));
+/// assert_eq!(1.0, dot_slice_with(&t, &u, Summation::Kahan));
+/// assert_eq!(1.0, dot_slice_with(&t, &u, Summation::Dot2));
+/// ```
+pub fn dot_slice_with<T: Float>(a: &[T], b: &[T], how: Summation) -> T {
+    match how {
+        Summation::Naive => dot_slice(a, b),
+        Summation::Kahan => dot_slice_kahan(a, b),
+        Summation::Pairwise => dot_slice_pairwise(a, b),
+        Summation::Widened => T::from_f64(widened(a, b)),
+        Summation::Dot2 => dot_slice_dot2(a, b),
+    }
+}
+
+/// # dot_slice_kahan()
+/// Takes a reference to a couple of float slices.
+///
+/// Returns the dot product with compensated
+/// (Kahan-Babuska-Neumaier) summation, so the
+/// little bits lost on each add get put back.
+///
+/// Only calculates for the shortest slice.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::dot_slice_kahan;
+///
+/// let t = [1.0, 1e100, 1.0, -1e100];
+/// let u = [1.0; 4];
+/// assert_eq!(2.0, dot_slice_kahan(&t, &u));
+/// ```
+pub fn dot_slice_kahan<T: Float>(a: &[T], b: &[T]) -> T {
+    let mut sum = T::ZERO;
+    let mut c = T::ZERO;
+    for (&x, &y) in a.iter().zip(b) {
+        let p = x * y;
+        let t = sum + p;
+        if sum.abs() >= p.abs() {
+            c = c + ((sum - t) + p);
+        } else {
+            c = c + ((p - t) + sum);
+        }
+        sum = t;
+    }
+    sum + c
+}
+
+/// # dot_slice_pairwise()
+/// Takes a reference to a couple of float slices.
+///
+/// Returns the dot product added up pairwise: the
+/// slices get halved until they're small, and the
+/// halves get summed together. Nearly as quick as
+/// the naive loop and a lot more accurate on long
+/// slices.
+///
+/// Only calculates for the shortest slice.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::dot_slice_pairwise;
+///
+/// let t = vec![0.1f32; 1_000_000];
+/// let u = vec![1.0f32; 1_000_000];
+/// let q = dot_slice_pairwise(&t, &u);
+/// assert!((q - 100_000.0).abs() < 1.0);
+/// ```
+pub fn dot_slice_pairwise<T: Float>(a: &[T], b: &[T]) -> T {
+    let len = a.len().min(b.len());
+    pairwise(&a[..len], &b[..len])
+}
+
+fn pairwise<T: Float>(a: &[T], b: &[T]) -> T {
+    if a.len() <= PAIRWISE_BLOCK {
+        return a.iter().zip(b).fold(T::ZERO, |acc, (&x, &y)| acc + x * y);
+    }
+    let mid = a.len() / 2;
+    pairwise(&a[..mid], &b[..mid]) + pairwise(&a[mid..], &b[mid..])
+}
+
+/// # dot_slice_f64acc()
+/// Takes a reference to a couple of f32 slices.
+///
+/// Returns their dot product, worked out in f64
+/// the whole way through. Round it back to f32
+/// yourself if that's what you need.
+///
+/// Only calculates for the shortest slice.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::dot_slice_f64acc;
+///
+/// let t = [16_777_216.0f32, 1.0, 1.0];
+/// let u = [1.0f32; 3];
+/// assert_eq!(16_777_218.0, dot_slice_f64acc(&t, &u));
+/// ```
+pub fn dot_slice_f64acc(a: &[f32], b: &[f32]) -> f64 {
+    widened(a, b)
+}
+
+fn widened<T: Float>(a: &[T], b: &[T]) -> f64 {
+    a.iter()
+        .zip(b)
+        .fold(0.0, |acc, (&x, &y)| acc + x.to_f64() * y.to_f64())
+}
+
+/// # dot_slice_dot2()
+/// Takes a reference to a couple of float slices.
+///
+/// Returns the dot product using Ogita, Rump and
+/// Oishi's Dot2. Every product and sum gets split
+/// into a result and its exact rounding error, and
+/// the errors get added back at the end.
+///
+/// The answer is as good as if you'd worked in twice
+/// the precision: off by at most about one rounding
+/// of the result, plus n² times eps² of Σ|aᵢbᵢ|.
+///
+/// Only calculates for the shortest slice.
+///
+/// ## Example:
+/// ```rust
+/// use slicenator::dot_slice_dot2;
+///
+/// let t = [1e16, 1.0, -1e16];
+/// let u = [1.0, 1.0, 1.0];
+/// assert_eq!(1.0, dot_slice_dot2(&t, &u));
+/// ```
+pub fn dot_slice_dot2<T: Float>(a: &[T], b: &[T]) -> T {
+    let mut s = T::ZERO;
+    let mut c = T::ZERO;
+    for (&x, &y) in a.iter().zip(b) {
+        let (p, pe) = two_product(x, y);
+        let (t, se) = two_sum(s, p);
+        s = t;
+        c = c + (pe + se);
+    }
+    s + c
+}
+
+/// Error free product: `p + e == x * y` exactly.
+fn two_product<T: Float>(x: T, y: T) -> (T, T) {
+    let p = x * y;
+    (p, x.mul_add(y, -p))
+}
+
+/// Error free sum: `s + e == x + y` exactly.
+fn two_sum<T: Float>(x: T, y: T) -> (T, T) {
+    let s = x + y;
+    let z = s - x;
+    (s, (x - (s - z)) + (y - z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Dot product with an exactly known answer: pairs that cancel
+    /// hugely, plus a small tail summing to 10.
+    fn nasty() -> (Vec<f64>, Vec<f64>) {
+        let mut a = Vec::new();
+        let mut b = Vec::new();
+        for i in 0..50 {
+            let big = 2f64.powi(40 + i % 10);
+            a.extend([big, 1.0, -big]);
+            b.extend([3.0, 0.2, 3.0]);
+        }
+        (a, b)
+    }
+
+    #[test]
+    fn summation_check() {
+        let (a, b) = nasty();
+        let exact = 10.0;
+        assert!((dot_slice_with(&a, &b, Summation::Kahan) - exact).abs() < 1e-9);
+        assert!((dot_slice_with(&a, &b, Summation::Dot2) - exact).abs() < 1e-12);
+        assert_eq!(dot_slice(&a, &b), dot_slice_with(&a, &b, Summation::Naive));
+
+        let t = [16_777_216.0f32, 1.0, 1.0, -16_777_216.0];
+        let u = [1.0f32; 4];
+        assert_eq!(0.0, dot_slice_with(&t, &u, Summation::Naive));
+        assert_eq!(2.0, dot_slice_with(&t, &u, Summation::Widened));
+    }
+
+    #[test]
+    fn shortest_slice_check() {
+        let t = [1.0, 2.0, 3.0];
+        let u = [4.0, 5.0];
+        for how in [
+            Summation::Naive,
+            Summation::Kahan,
+            Summation::Pairwise,
+            Summation::Widened,
+            Summation::Dot2,
+        ] {
+            assert_eq!(14.0, dot_slice_with(&t, &u, how));
+            assert_eq!(0.0, dot_slice_with::<f32>(&[], &[1.0], how));
+        }
+    }
+
+    #[test]
+    fn pairwise_check() {
+        let t: Vec<f32> = (0..100_000).map(|i| (i % 7) as f32 * 0.1).collect();
+        let u = vec![1.0f32; t.len()];
+        let exact = dot_slice_f64acc(&t, &u);
+        let p = dot_slice_pairwise(&t, &u) as f64;
+        assert!((p - exact).abs() / exact < 1e-6);
+    }
+
+    #[test]
+    fn two_sum_product_check() {
+        let (s, e) = two_sum(1e16, 1.0);
+        assert_eq!((1e16, 1.0), (s, e));
+        let x = 1.0 + f64::EPSILON;
+        let (p, e) = two_product(x, x);
+        assert_eq!(1.0 + 2.0 * f64::EPSILON, p);
+        assert_eq!(f64::EPSILON * f64::EPSILON, e);
+    }
+}
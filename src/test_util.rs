@@ -0,0 +1,6 @@
+//! Helpers shared by the unit tests.
+
+/// Same length and every pair within `tol`.
+pub(crate) fn close(a: &[f64], b: &[f64], tol: f64) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < tol)
+}